@@ -121,6 +121,13 @@ enum SessionCommand {
             long_help = "Path to save the exported Markdown. If not provided, output will be sent to stdout"
         )]
         output: Option<PathBuf>,
+
+        #[arg(
+            long,
+            help = "Include full tool calls and output",
+            long_help = "Include the full arguments and output of every tool call. By default each tool call is summarized in a single line and its output is left out"
+        )]
+        include_tools: bool,
     },
 }
 
@@ -765,7 +772,11 @@ pub async fn cli() -> Result<()> {
                     handle_session_remove(id, regex)?;
                     return Ok(());
                 }
-                Some(SessionCommand::Export { identifier, output }) => {
+                Some(SessionCommand::Export {
+                    identifier,
+                    output,
+                    include_tools,
+                }) => {
                     let session_identifier = if let Some(id) = identifier {
                         extract_identifier(id)
                     } else {
@@ -779,7 +790,11 @@ pub async fn cli() -> Result<()> {
                         }
                     };
 
-                    crate::commands::session::handle_session_export(session_identifier, output)?;
+                    crate::commands::session::handle_session_export(
+                        session_identifier,
+                        output,
+                        include_tools,
+                    )?;
                     Ok(())
                 }
                 None => {
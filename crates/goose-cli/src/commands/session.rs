@@ -170,7 +170,11 @@ pub fn handle_session_list(verbose: bool, format: String, ascending: bool) -> Re
 ///
 /// This function directly reads messages from the session file and converts them to Markdown
 /// without creating an Agent or prompting about working directories.
-pub fn handle_session_export(identifier: Identifier, output_path: Option<PathBuf>) -> Result<()> {
+pub fn handle_session_export(
+    identifier: Identifier,
+    output_path: Option<PathBuf>,
+    include_tools: bool,
+) -> Result<()> {
     // Get the session file path
     let session_file_path = match goose::session::get_path(identifier.clone()) {
         Ok(path) => path,
@@ -195,8 +199,12 @@ pub fn handle_session_export(identifier: Identifier, output_path: Option<PathBuf
     };
 
     // Generate the markdown content using the export functionality
-    let markdown =
-        export_session_to_markdown(messages.messages().clone(), &session_file_path, None);
+    let markdown = export_session_to_markdown(
+        messages.messages().clone(),
+        &session_file_path,
+        None,
+        include_tools,
+    );
 
     // Output the markdown
     if let Some(output) = output_path {
@@ -213,11 +221,13 @@ pub fn handle_session_export(identifier: Identifier, output_path: Option<PathBuf
 /// Convert a list of messages to markdown format for session export
 ///
 /// This function handles the formatting of a complete session including headers,
-/// message organization, and proper tool request/response pairing.
+/// message organization, and proper tool request/response pairing. Without
+/// `include_tools`, tool calls are summarized in a line each and their output is left out.
 fn export_session_to_markdown(
     messages: Vec<goose::conversation::message::Message>,
     session_file: &Path,
     session_name_override: Option<&str>,
+    include_tools: bool,
 ) -> String {
     let mut markdown_output = String::new();
 
@@ -254,8 +264,12 @@ fn export_session_to_markdown(
         // don't create a new User section - we'll attach the responses to the tool calls
         if skip_next_if_tool_response && is_only_tool_response {
             // Export the tool responses without a User heading
-            markdown_output.push_str(&message_to_markdown(message, false));
-            markdown_output.push_str("\n\n---\n\n");
+            let responses = message_to_markdown(message, false, include_tools);
+            // Summarized exports drop successful output, leaving nothing to show
+            if !responses.is_empty() {
+                markdown_output.push_str(&responses);
+                markdown_output.push_str("\n\n---\n\n");
+            }
             skip_next_if_tool_response = false;
             continue;
         }
@@ -273,7 +287,7 @@ fn export_session_to_markdown(
         }
 
         // Add the message content
-        markdown_output.push_str(&message_to_markdown(message, false));
+        markdown_output.push_str(&message_to_markdown(message, false, include_tools));
         markdown_output.push_str("\n\n---\n\n");
 
         // Check if this message has any tool requests, to handle the next message differently
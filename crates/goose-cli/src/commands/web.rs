@@ -492,6 +492,7 @@ async fn process_message_streaming(
         execution_mode: None,
         max_turns: None,
         retry_config: None,
+        max_history_messages: None,
     };
 
     match agent
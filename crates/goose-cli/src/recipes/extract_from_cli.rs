@@ -34,6 +34,8 @@ pub fn extract_recipe_info_from_cli(
                         values: None,
                         sequential_when_repeated: true,
                         description: None,
+                        env: None,
+                        cwd: None,
                     };
                     all_sub_recipes.push(additional_sub_recipe);
                 }
@@ -307,6 +307,8 @@ mod tests {
                 values: None,
                 sequential_when_repeated: false,
                 description: None,
+                env: None,
+                cwd: None,
             }]),
             context: None,
             settings: None,
@@ -298,6 +298,12 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> Session {
             Some(identifier) => identifier,
             None => Identifier::Name(session::generate_session_id()),
         };
+        if let Identifier::Name(name) = &id {
+            if let Err(e) = session::validate_new_session_name(name) {
+                output::render_error(&format!("Failed to create session: {}", e));
+                process::exit(1);
+            }
+        }
 
         // Just get the path - file will be created when needed
         match session::get_path(id) {
@@ -5,6 +5,7 @@ use serde_json::Value;
 
 const MAX_STRING_LENGTH_MD_EXPORT: usize = 4096; // Generous limit for export
 const REDACTED_PREFIX_LENGTH: usize = 100; // Show first 100 chars before trimming
+const TOOL_SUMMARY_LENGTH: usize = 80; // Longest argument shown in a one-line tool summary
 
 fn value_to_simple_markdown_string(value: &Value, export_full_strings: bool) -> String {
     match value {
@@ -198,6 +199,49 @@ pub fn tool_request_to_markdown(req: &ToolRequest, export_all_content: bool) ->
     md
 }
 
+/// One-line summary of a tool call, e.g. "ran shell: `npm test`", for exports meant for
+/// readers who don't need the full arguments and output
+pub fn tool_request_summary(req: &ToolRequest) -> String {
+    let call = match &req.tool_call {
+        Ok(call) => call,
+        Err(e) => {
+            return format!(
+                "*invalid tool call: {}*",
+                safe_truncate(&e.to_string(), TOOL_SUMMARY_LENGTH)
+            )
+        }
+    };
+    let tool_name = call.name.rsplit("__").next().unwrap_or(&call.name);
+    let arg = |key: &str| call.arguments.get(key).and_then(Value::as_str);
+
+    let detail = match call.name.as_str() {
+        "developer__shell" => arg("command").map(str::to_string),
+        "developer__text_editor" => match (arg("command"), arg("path")) {
+            (Some(command), Some(path)) => Some(format!("{} {}", command, path)),
+            (None, Some(path)) => Some(path.to_string()),
+            _ => None,
+        },
+        // Other tools are summarized by their arguments in compact form
+        _ => call
+            .arguments
+            .as_object()
+            .filter(|args| !args.is_empty())
+            .map(|_| call.arguments.to_string()),
+    };
+
+    match detail {
+        Some(detail) => {
+            let detail = detail.split_whitespace().collect::<Vec<_>>().join(" ");
+            format!(
+                "*ran {}:* `{}`",
+                tool_name,
+                safe_truncate(&detail, TOOL_SUMMARY_LENGTH).replace('`', "'")
+            )
+        }
+        None => format!("*ran {}*", tool_name),
+    }
+}
+
 pub fn tool_response_to_markdown(resp: &ToolResponse, export_all_content: bool) -> String {
     let mut md = String::new();
     md.push_str("#### Tool Response:\n");
@@ -314,7 +358,13 @@ pub fn tool_response_to_markdown(resp: &ToolResponse, export_all_content: bool)
     md
 }
 
-pub fn message_to_markdown(message: &Message, export_all_content: bool) -> String {
+/// Render a message as Markdown. Without `include_tools`, tool calls are collapsed into
+/// one-line summaries and only failed tool responses are mentioned.
+pub fn message_to_markdown(
+    message: &Message,
+    export_all_content: bool,
+    include_tools: bool,
+) -> String {
     let mut md = String::new();
     for content in &message.content {
         match content {
@@ -322,10 +372,22 @@ pub fn message_to_markdown(message: &Message, export_all_content: bool) -> Strin
                 md.push_str(&text.text);
                 md.push_str("\n\n");
             }
+            MessageContent::ToolRequest(req) if !include_tools => {
+                md.push_str(&tool_request_summary(req));
+                md.push_str("\n\n");
+            }
             MessageContent::ToolRequest(req) => {
                 md.push_str(&tool_request_to_markdown(req, export_all_content));
                 md.push('\n');
             }
+            MessageContent::ToolResponse(resp) if !include_tools => {
+                if let Err(e) = &resp.tool_result {
+                    md.push_str(&format!(
+                        "*tool failed: {}*\n\n",
+                        safe_truncate(&e.to_string(), TOOL_SUMMARY_LENGTH)
+                    ));
+                }
+            }
             MessageContent::ToolResponse(resp) => {
                 md.push_str(&tool_response_to_markdown(resp, export_all_content));
                 md.push('\n');
@@ -365,7 +427,7 @@ mod tests {
     use super::*;
     use goose::conversation::message::{Message, ToolRequest, ToolResponse};
     use mcp_core::tool::ToolCall;
-    use rmcp::model::{Content, RawTextContent, TextContent};
+    use rmcp::model::{Content, ErrorCode, ErrorData, RawTextContent, TextContent};
     use serde_json::json;
 
     #[test]
@@ -564,7 +626,7 @@ mod tests {
     fn test_message_to_markdown_text() {
         let message = Message::user().with_text("Hello, this is a test message");
 
-        let result = message_to_markdown(&message, true);
+        let result = message_to_markdown(&message, true, true);
         assert_eq!(result, "Hello, this is a test message");
     }
 
@@ -577,7 +639,7 @@ mod tests {
 
         let message = Message::assistant().with_tool_request("test-id", Ok(tool_call));
 
-        let result = message_to_markdown(&message, true);
+        let result = message_to_markdown(&message, true, true);
         assert!(result.contains("#### Tool Call: `test_tool`"));
         assert!(result.contains("**param**"));
     }
@@ -587,7 +649,7 @@ mod tests {
         let message = Message::assistant()
             .with_thinking("I need to analyze this problem...", "test-signature");
 
-        let result = message_to_markdown(&message, true);
+        let result = message_to_markdown(&message, true, true);
         assert!(result.contains("**Thinking:**"));
         assert!(result.contains("> I need to analyze this problem..."));
     }
@@ -596,11 +658,51 @@ mod tests {
     fn test_message_to_markdown_redacted_thinking() {
         let message = Message::assistant().with_redacted_thinking("redacted-data");
 
-        let result = message_to_markdown(&message, true);
+        let result = message_to_markdown(&message, true, true);
         assert!(result.contains("**Thinking:**"));
         assert!(result.contains("> *Thinking was redacted*"));
     }
 
+    #[test]
+    fn test_message_to_markdown_summarizes_tools() {
+        let shell_call = ToolCall {
+            name: "developer__shell".to_string(),
+            arguments: json!({"command": "npm   test\n"}),
+        };
+        let message = Message::assistant()
+            .with_text("Running the tests")
+            .with_tool_request("shell-id", Ok(shell_call));
+
+        let result = message_to_markdown(&message, false, false);
+        assert_eq!(result, "Running the tests\n\n*ran shell:* `npm test`");
+
+        let other_call = ToolCall {
+            name: "memory__remember".to_string(),
+            arguments: json!({}),
+        };
+        let message = Message::assistant().with_tool_request("other-id", Ok(other_call));
+        assert_eq!(
+            message_to_markdown(&message, false, false),
+            "*ran remember*"
+        );
+
+        // Successful output is dropped, failures are kept as a single line
+        let ok = Message::user().with_tool_response("shell-id", Ok(vec![Content::text("ok")]));
+        assert_eq!(message_to_markdown(&ok, false, false), "");
+        let failed = Message::user().with_tool_response(
+            "shell-id",
+            Err(ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                "exit code 1",
+                None,
+            )),
+        );
+        assert_eq!(
+            message_to_markdown(&failed, false, false),
+            "*tool failed: -32603: exit code 1*"
+        );
+    }
+
     #[test]
     fn test_recursive_value_to_markdown() {
         // Test that complex nested structures are properly handled with recursion
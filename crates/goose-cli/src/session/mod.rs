@@ -8,7 +8,7 @@ mod task_execution_display;
 mod thinking;
 
 use crate::session::task_execution_display::{
-    format_task_execution_notification, TASK_EXECUTION_NOTIFICATION_TYPE,
+    format_task_execution_notification, TaskDeltaState, TASK_EXECUTION_NOTIFICATION_TYPE,
 };
 use goose::conversation::Conversation;
 use std::io::Write;
@@ -16,6 +16,7 @@ use std::io::Write;
 pub use self::export::message_to_markdown;
 pub use builder::{build_session, SessionBuilderConfig, SessionSettings};
 use console::Color;
+use goose::agents::usage_alert::USAGE_ALERT_NOTIFICATION_TYPE;
 use goose::agents::AgentEvent;
 use goose::permission::permission_confirmation::PrincipalType;
 use goose::permission::Permission;
@@ -66,6 +67,7 @@ pub struct Session {
     max_turns: Option<u32>,
     edit_mode: Option<EditMode>,
     retry_config: Option<RetryConfig>,
+    task_deltas: TaskDeltaState,
 }
 
 // Cache structure for completion data
@@ -152,6 +154,7 @@ impl Session {
             max_turns,
             edit_mode,
             retry_config,
+            task_deltas: TaskDeltaState::default(),
         }
     }
 
@@ -748,39 +751,38 @@ impl Session {
                                 let session_file_path = session::storage::get_path(
                                     session::storage::Identifier::Path(session_file.to_path_buf()),
                                 )?;
-                                let mut metadata =
-                                    session::storage::read_metadata(&session_file_path)?;
-
-                                // Update token counts with the summarization usage
-                                // Use output tokens as total since that's what's actually in the context going forward
-                                let summary_tokens = usage.usage.output_tokens.unwrap_or(0);
-                                metadata.total_tokens = Some(summary_tokens);
-                                metadata.input_tokens = None; // Clear input tokens since we now have a summary
-                                metadata.output_tokens = Some(summary_tokens);
-                                metadata.message_count = self.messages.len();
-
-                                // Update accumulated tokens (add the summarization cost)
-                                let accumulate = |a: Option<i32>, b: Option<i32>| -> Option<i32> {
-                                    match (a, b) {
-                                        (Some(x), Some(y)) => Some(x + y),
-                                        _ => a.or(b),
-                                    }
-                                };
-                                metadata.accumulated_total_tokens = accumulate(
-                                    metadata.accumulated_total_tokens,
-                                    usage.usage.total_tokens,
-                                );
-                                metadata.accumulated_input_tokens = accumulate(
-                                    metadata.accumulated_input_tokens,
-                                    usage.usage.input_tokens,
-                                );
-                                metadata.accumulated_output_tokens = accumulate(
-                                    metadata.accumulated_output_tokens,
-                                    usage.usage.output_tokens,
-                                );
-
-                                session::storage::update_metadata(&session_file_path, &metadata)
-                                    .await?;
+                                let messages_len = self.messages.len();
+                                session::storage::modify_metadata(&session_file_path, |metadata| {
+                                    // Update token counts with the summarization usage
+                                    // Use output tokens as total since that's what's actually in the context going forward
+                                    let summary_tokens = usage.usage.output_tokens.unwrap_or(0);
+                                    metadata.total_tokens = Some(summary_tokens);
+                                    metadata.input_tokens = None; // Clear input tokens since we now have a summary
+                                    metadata.output_tokens = Some(summary_tokens);
+                                    metadata.message_count = messages_len;
+
+                                    // Update accumulated tokens (add the summarization cost)
+                                    let accumulate =
+                                        |a: Option<i32>, b: Option<i32>| -> Option<i32> {
+                                            match (a, b) {
+                                                (Some(x), Some(y)) => Some(x + y),
+                                                _ => a.or(b),
+                                            }
+                                        };
+                                    metadata.accumulated_total_tokens = accumulate(
+                                        metadata.accumulated_total_tokens,
+                                        usage.usage.total_tokens,
+                                    );
+                                    metadata.accumulated_input_tokens = accumulate(
+                                        metadata.accumulated_input_tokens,
+                                        usage.usage.input_tokens,
+                                    );
+                                    metadata.accumulated_output_tokens = accumulate(
+                                        metadata.accumulated_output_tokens,
+                                        usage.usage.output_tokens,
+                                    );
+                                })
+                                .await?;
                             }
                         }
 
@@ -919,6 +921,7 @@ impl Session {
                 execution_mode: None,
                 max_turns: self.max_turns,
                 retry_config: self.retry_config.clone(),
+                max_history_messages: None,
             }
         });
         let mut stream = self
@@ -1162,6 +1165,9 @@ impl Session {
                                                     Some("message_processing") | Some("turn_progress") => {
                                                         format!("💭 {}", msg)
                                                     }
+                                                    Some(USAGE_ALERT_NOTIFICATION_TYPE) => {
+                                                        format!("⚠️  {}", msg)
+                                                    }
                                                     Some("response_generated") => {
                                                         // Check verbosity setting for subagent response content
                                                         let config = Config::global();
@@ -1190,7 +1196,7 @@ impl Session {
                                             } else if let Some(Value::String(output)) = o.get("output") {
                                                 // Fallback for other MCP notification types
                                                 (output.to_owned(), None, None)
-                                            } else if let Some(result) = format_task_execution_notification(data) {
+                                            } else if let Some(result) = format_task_execution_notification(data, &mut self.task_deltas) {
                                                 result
                                             } else {
                                                 (data.to_string(), None, None)
@@ -1220,6 +1226,9 @@ impl Session {
                                                 print!("{}", formatted_message);
                                                 std::io::stdout().flush().unwrap();
                                             }
+                                        } else if notification_type == USAGE_ALERT_NOTIFICATION_TYPE {
+                                            let _ = progress_bars.hide();
+                                            println!("{}", console::style(&formatted_message).yellow());
                                         }
                                     }
                                     else if output::is_showing_thinking() {
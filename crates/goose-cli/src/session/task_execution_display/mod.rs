@@ -17,6 +17,13 @@ pub const TASK_EXECUTION_NOTIFICATION_TYPE: &str = "task_execution";
 
 static INITIAL_SHOWN: AtomicBool = AtomicBool::new(false);
 
+/// Task list rebuilt from a `tasks_started` event and the `tasks_delta` events after it.
+/// Each session keeps its own, so task lists from different sessions never mix.
+#[derive(Debug, Default)]
+pub struct TaskDeltaState {
+    tasks: Vec<TaskInfo>,
+}
+
 fn format_result_data_for_display(result_data: &Value) -> String {
     match result_data {
         Value::String(s) => s.to_string(),
@@ -51,6 +58,7 @@ fn process_output_for_display(output: &str) -> String {
 
 pub fn format_task_execution_notification(
     data: &Value,
+    deltas: &mut TaskDeltaState,
 ) -> Option<(String, Option<String>, Option<String>)> {
     if let Ok(event) = serde_json::from_value::<TaskExecutionNotificationEvent>(data.clone()) {
         return Some(match event {
@@ -59,8 +67,10 @@ pub fn format_task_execution_notification(
                 None,
                 Some(TASK_EXECUTION_NOTIFICATION_TYPE.to_string()),
             ),
-            TaskExecutionNotificationEvent::TasksUpdate { .. } => {
-                let formatted_display = format_tasks_update_from_event(&event);
+            TaskExecutionNotificationEvent::TasksUpdate { .. }
+            | TaskExecutionNotificationEvent::TasksStarted { .. }
+            | TaskExecutionNotificationEvent::TasksDelta { .. } => {
+                let formatted_display = format_tasks_update_from_event(&event, deltas);
                 (
                     formatted_display,
                     None,
@@ -80,42 +90,70 @@ pub fn format_task_execution_notification(
     None
 }
 
-fn format_tasks_update_from_event(event: &TaskExecutionNotificationEvent) -> String {
-    if let TaskExecutionNotificationEvent::TasksUpdate { stats, tasks } = event {
-        let mut display = String::new();
-
-        if !INITIAL_SHOWN.swap(true, Ordering::SeqCst) {
-            display.push_str(CLEAR_SCREEN);
-            display.push_str("🎯 Task Execution Dashboard\n");
-            display.push_str("═══════════════════════════\n\n");
-        } else {
-            display.push_str(MOVE_TO_PROGRESS_LINE);
+// Replace tasks with the same id as an updated one, keeping their position, and append
+// any the list didn't have yet
+fn apply_task_delta(known: &mut Vec<TaskInfo>, updates: &[TaskInfo]) {
+    for update in updates {
+        match known.iter_mut().find(|task| task.id == update.id) {
+            Some(task) => *task = update.clone(),
+            None => known.push(update.clone()),
         }
+    }
+}
 
-        display.push_str(&format!(
-            "📊 Progress: {} total | ⏳ {} pending | 🏃 {} running | ✅ {} completed | ❌ {} failed", 
-            stats.total, stats.pending, stats.running, stats.completed, stats.failed
-        ));
-        display.push_str(&format!("{}\n\n", CLEAR_TO_EOL));
-
-        let mut sorted_tasks = tasks.clone();
-        sorted_tasks.sort_by(|a, b| a.id.cmp(&b.id));
-
-        for task in sorted_tasks {
-            display.push_str(&format_task_display(&task));
+fn format_tasks_update_from_event(
+    event: &TaskExecutionNotificationEvent,
+    deltas: &mut TaskDeltaState,
+) -> String {
+    let (stats, tasks) = match event {
+        TaskExecutionNotificationEvent::TasksUpdate { stats, tasks } => (stats, tasks.clone()),
+        TaskExecutionNotificationEvent::TasksStarted { stats, tasks } => {
+            deltas.tasks = tasks.clone();
+            (stats, tasks.clone())
         }
+        TaskExecutionNotificationEvent::TasksDelta { stats, tasks } => {
+            apply_task_delta(&mut deltas.tasks, tasks);
+            (stats, deltas.tasks.clone())
+        }
+        _ => return String::new(),
+    };
 
-        display.push_str(CLEAR_BELOW);
-        display
+    let mut display = String::new();
+
+    if !INITIAL_SHOWN.swap(true, Ordering::SeqCst) {
+        display.push_str(CLEAR_SCREEN);
+        display.push_str("🎯 Task Execution Dashboard\n");
+        display.push_str("═══════════════════════════\n\n");
     } else {
-        String::new()
+        display.push_str(MOVE_TO_PROGRESS_LINE);
+    }
+
+    display.push_str(&format!(
+        "📊 Progress: {} total | ⏳ {} pending | 🏃 {} running | ✅ {} completed | ❌ {} failed",
+        stats.total, stats.pending, stats.running, stats.completed, stats.failed
+    ));
+    if stats.cancelled > 0 {
+        display.push_str(&format!(" | 🚫 {} cancelled", stats.cancelled));
     }
+    if let Some(elapsed_secs) = stats.elapsed_secs {
+        display.push_str(&format!(" | ⏱️  {:.1}s elapsed", elapsed_secs));
+    }
+    if let Some(eta_secs) = stats.eta_secs {
+        display.push_str(&format!(" | ~{:.0}s left", eta_secs));
+    }
+    display.push_str(&format!("{}\n\n", CLEAR_TO_EOL));
+
+    display.push_str(&format_task_list(&tasks));
+    display.push_str(CLEAR_BELOW);
+    display
 }
 
 fn format_tasks_complete_from_event(event: &TaskExecutionNotificationEvent) -> String {
     if let TaskExecutionNotificationEvent::TasksComplete {
         stats,
         failed_tasks,
+        failure_groups,
+        batch_id,
     } = event
     {
         let mut summary = String::new();
@@ -125,6 +163,9 @@ fn format_tasks_complete_from_event(event: &TaskExecutionNotificationEvent) -> S
         summary.push_str(&format!("Total Tasks: {}\n", stats.total));
         summary.push_str(&format!("✅ Completed: {}\n", stats.completed));
         summary.push_str(&format!("❌ Failed: {}\n", stats.failed));
+        if stats.cancelled > 0 {
+            summary.push_str(&format!("🚫 Cancelled: {}\n", stats.cancelled));
+        }
         summary.push_str(&format!("📈 Success Rate: {:.1}%\n", stats.success_rate));
 
         if !failed_tasks.is_empty() {
@@ -137,6 +178,18 @@ fn format_tasks_complete_from_event(event: &TaskExecutionNotificationEvent) -> S
             }
         }
 
+        // Only worth showing when several failures share a cause
+        if failure_groups.len() < failed_tasks.len() {
+            summary.push_str("\n📊 Failures by error:\n");
+            for group in failure_groups {
+                summary.push_str(&format!("   {}× {}\n", group.count, group.signature));
+            }
+        }
+
+        if let Some(batch_id) = batch_id {
+            summary.push_str(&format!("\n💾 Results saved as batch {}\n", batch_id));
+        }
+
         summary.push_str("\n📝 Generating summary...\n");
         summary
     } else {
@@ -144,6 +197,26 @@ fn format_tasks_complete_from_event(event: &TaskExecutionNotificationEvent) -> S
     }
 }
 
+// Tasks without a group come first, then each group under its own heading. Tasks keep
+// the order the tracker sent them in otherwise.
+fn format_task_list(tasks: &[TaskInfo]) -> String {
+    let mut sorted_tasks = tasks.to_vec();
+    sorted_tasks.sort_by(|a, b| a.group.cmp(&b.group));
+
+    let mut display = String::new();
+    let mut current_group = None;
+    for task in &sorted_tasks {
+        if let Some(group) = &task.group {
+            if current_group != Some(group) {
+                display.push_str(&format!("📁 {}{}\n", group, CLEAR_TO_EOL));
+                current_group = Some(group);
+            }
+        }
+        display.push_str(&format_task_display(task));
+    }
+    display
+}
+
 fn format_task_display(task: &TaskInfo) -> String {
     let mut task_display = String::new();
 
@@ -152,6 +225,7 @@ fn format_task_display(task: &TaskInfo) -> String {
         TaskStatus::Running => "🏃",
         TaskStatus::Completed => "✅",
         TaskStatus::Failed => "❌",
+        TaskStatus::Cancelled => "🚫",
     };
 
     task_display.push_str(&format!(
@@ -71,7 +71,7 @@ fn test_format_task_execution_notification_line_output() {
         "output": "Hello World"
     });
 
-    let result = format_task_execution_notification(&data);
+    let result = format_task_execution_notification(&data, &mut TaskDeltaState::default());
     assert!(result.is_some());
 
     let (formatted, second, third) = result.unwrap();
@@ -86,14 +86,15 @@ fn test_format_task_execution_notification_invalid_data() {
         "invalid": "structure"
     });
 
-    let result = format_task_execution_notification(&invalid_data);
+    let result = format_task_execution_notification(&invalid_data, &mut TaskDeltaState::default());
     assert_eq!(result, None);
 
     let incomplete_data = json!({
         "subtype": "line_output"
     });
 
-    let result = format_task_execution_notification(&incomplete_data);
+    let result =
+        format_task_execution_notification(&incomplete_data, &mut TaskDeltaState::default());
     assert_eq!(result, None);
 }
 
@@ -101,7 +102,7 @@ fn test_format_task_execution_notification_invalid_data() {
 fn test_format_tasks_update_from_event() {
     INITIAL_SHOWN.store(false, Ordering::SeqCst);
 
-    let stats = TaskExecutionStats::new(3, 1, 1, 1, 0);
+    let stats = TaskExecutionStats::new(3, 1, 1, 1, 0, 0).with_timing(Some(4.5), Some(4.6));
     let tasks = vec![
         TaskInfo {
             id: "task-1".to_string(),
@@ -113,6 +114,7 @@ fn test_format_tasks_update_from_event() {
             task_metadata: "param=value".to_string(),
             error: None,
             result_data: None,
+            group: None,
         },
         TaskInfo {
             id: "task-2".to_string(),
@@ -124,11 +126,13 @@ fn test_format_tasks_update_from_event() {
             task_metadata: "".to_string(),
             error: None,
             result_data: Some(json!({"result": "success"})),
+            group: None,
         },
     ];
 
     let event = TaskExecutionNotificationEvent::TasksUpdate { stats, tasks };
-    let result = format_tasks_update_from_event(&event);
+    let mut deltas = TaskDeltaState::default();
+    let result = format_tasks_update_from_event(&event, &mut deltas);
 
     assert!(result.contains("🎯 Task Execution Dashboard"));
     assert!(result.contains("═══════════════════════════"));
@@ -137,20 +141,22 @@ fn test_format_tasks_update_from_event() {
     assert!(result.contains("🏃 1 running"));
     assert!(result.contains("✅ 1 completed"));
     assert!(result.contains("❌ 0 failed"));
+    assert!(result.contains("4.5s elapsed"));
+    assert!(result.contains("~5s left"));
     assert!(result.contains("🏃 test-task"));
     assert!(result.contains("✅ another-task"));
     assert!(result.contains("📋 Parameters: param=value"));
     assert!(result.contains("⏱️  1.5s"));
     assert!(result.contains("💬 Processing..."));
 
-    let result2 = format_tasks_update_from_event(&event);
+    let result2 = format_tasks_update_from_event(&event, &mut deltas);
     assert!(!result2.contains("🎯 Task Execution Dashboard"));
     assert!(result2.contains(MOVE_TO_PROGRESS_LINE));
 }
 
 #[test]
 fn test_format_tasks_complete_from_event() {
-    let stats = TaskCompletionStats::new(5, 4, 1);
+    let stats = TaskCompletionStats::new(5, 4, 1, 0);
     let failed_tasks = vec![FailedTaskInfo {
         id: "task-3".to_string(),
         name: "failed-task".to_string(),
@@ -160,6 +166,8 @@ fn test_format_tasks_complete_from_event() {
     let event = TaskExecutionNotificationEvent::TasksComplete {
         stats,
         failed_tasks,
+        failure_groups: vec![],
+        batch_id: None,
     };
     let result = format_tasks_complete_from_event(&event);
 
@@ -175,14 +183,51 @@ fn test_format_tasks_complete_from_event() {
     assert!(result.contains("📝 Generating summary..."));
 }
 
+#[test]
+fn test_format_tasks_complete_groups_repeated_failures() {
+    let failed = |id: &str, error: &str| FailedTaskInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        error: Some(error.to_string()),
+    };
+    let event = TaskExecutionNotificationEvent::tasks_complete(
+        TaskCompletionStats::new(3, 0, 3, 0),
+        vec![
+            failed("task-1", "Timed out after 30s"),
+            failed("task-2", "Timed out after 45s"),
+            failed("task-3", "Auth error"),
+        ],
+    );
+    let result = format_tasks_complete_from_event(&event);
+
+    assert!(result.contains("📊 Failures by error:"));
+    assert!(result.contains("2× Timed out after <n>"));
+    assert!(result.contains("1× Auth error"));
+}
+
+#[test]
+fn test_format_tasks_complete_reports_cancelled() {
+    let event = TaskExecutionNotificationEvent::tasks_complete(
+        TaskCompletionStats::new(4, 1, 0, 3),
+        vec![],
+    );
+    let result = format_tasks_complete_from_event(&event);
+
+    assert!(result.contains("❌ Failed: 0"));
+    assert!(result.contains("🚫 Cancelled: 3"));
+    assert!(!result.contains("❌ Failed Tasks:"));
+}
+
 #[test]
 fn test_format_tasks_complete_from_event_no_failures() {
-    let stats = TaskCompletionStats::new(3, 3, 0);
+    let stats = TaskCompletionStats::new(3, 3, 0, 0);
     let failed_tasks = vec![];
 
     let event = TaskExecutionNotificationEvent::TasksComplete {
         stats,
         failed_tasks,
+        failure_groups: vec![],
+        batch_id: None,
     };
     let result = format_tasks_complete_from_event(&event);
 
@@ -203,6 +248,7 @@ fn test_format_task_display_running() {
         task_metadata: "input=file.txt,output=result.json".to_string(),
         error: None,
         result_data: None,
+        group: None,
     };
 
     let result = format_task_display(&task);
@@ -213,6 +259,116 @@ fn test_format_task_display_running() {
     assert!(result.contains("💬 Processing data... ... Almost done..."));
 }
 
+#[test]
+fn test_format_task_list_groups_tasks() {
+    let task = |id: &str, group: Option<&str>| TaskInfo {
+        id: id.to_string(),
+        status: TaskStatus::Pending,
+        duration_secs: None,
+        current_output: String::new(),
+        task_type: "inline_recipe".to_string(),
+        task_name: id.to_string(),
+        task_metadata: String::new(),
+        error: None,
+        result_data: None,
+        group: group.map(String::from),
+    };
+
+    let result = format_task_list(&[
+        task("task-3", Some("review")),
+        task("task-1", Some("build")),
+        task("task-4", None),
+        task("task-2", Some("review")),
+    ]);
+
+    let position = |needle: &str| result.find(needle).unwrap();
+    assert_eq!(result.matches("📁 review").count(), 1);
+    assert!(position("task-4") < position("📁 build"));
+    assert!(position("📁 build") < position("task-1"));
+    assert!(position("task-1") < position("📁 review"));
+    assert!(position("📁 review") < position("task-3"));
+    assert!(position("task-3") < position("task-2"));
+}
+
+#[test]
+fn test_apply_task_delta() {
+    let task = |id: &str, status: TaskStatus| TaskInfo {
+        id: id.to_string(),
+        status,
+        duration_secs: None,
+        current_output: String::new(),
+        task_type: "inline_recipe".to_string(),
+        task_name: id.to_string(),
+        task_metadata: String::new(),
+        error: None,
+        result_data: None,
+        group: None,
+    };
+
+    let mut known = vec![
+        task("task-1", TaskStatus::Pending),
+        task("task-2", TaskStatus::Pending),
+    ];
+    apply_task_delta(
+        &mut known,
+        &[
+            task("task-2", TaskStatus::Running),
+            task("task-3", TaskStatus::Pending),
+        ],
+    );
+
+    let states: Vec<_> = known
+        .iter()
+        .map(|task| (task.id.as_str(), task.status.to_string()))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            ("task-1", "Pending".to_string()),
+            ("task-2", "Running".to_string()),
+            ("task-3", "Pending".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_task_deltas_are_kept_per_state() {
+    let task = |id: &str| TaskInfo {
+        id: id.to_string(),
+        status: TaskStatus::Pending,
+        duration_secs: None,
+        current_output: String::new(),
+        task_type: "inline_recipe".to_string(),
+        task_name: id.to_string(),
+        task_metadata: String::new(),
+        error: None,
+        result_data: None,
+        group: None,
+    };
+    let stats = || TaskExecutionStats::new(1, 1, 0, 0, 0, 0);
+
+    let mut first = TaskDeltaState::default();
+    let mut second = TaskDeltaState::default();
+    format_tasks_update_from_event(
+        &TaskExecutionNotificationEvent::TasksStarted {
+            stats: stats(),
+            tasks: vec![task("first-task")],
+        },
+        &mut first,
+    );
+
+    // A delta for another session only sees that session's tasks
+    let result = format_tasks_update_from_event(
+        &TaskExecutionNotificationEvent::TasksDelta {
+            stats: stats(),
+            tasks: vec![task("second-task")],
+        },
+        &mut second,
+    );
+    assert!(result.contains("second-task"));
+    assert!(!result.contains("first-task"));
+}
+
 #[test]
 fn test_format_task_display_completed() {
     let task = TaskInfo {
@@ -225,6 +381,7 @@ fn test_format_task_display_completed() {
         task_metadata: "".to_string(),
         error: None,
         result_data: Some(json!({"status": "success", "count": 42})),
+        group: None,
     };
 
     let result = format_task_display(&task);
@@ -250,6 +407,7 @@ fn test_format_task_display_failed() {
                 .to_string(),
         ),
         result_data: None,
+        group: None,
     };
 
     let result = format_task_display(&task);
@@ -272,6 +430,7 @@ fn test_format_task_display_pending() {
         task_metadata: "priority=high".to_string(),
         error: None,
         result_data: None,
+        group: None,
     };
 
     let result = format_task_display(&task);
@@ -296,6 +455,7 @@ fn test_format_task_display_empty_current_output() {
         task_metadata: "".to_string(),
         error: None,
         result_data: None,
+        group: None,
     };
 
     let result = format_task_display(&task);
@@ -0,0 +1,187 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use xcap::{Monitor, Window};
+
+/// How long window and monitor enumerations are reused, in milliseconds
+pub const DEFAULT_CAPTURE_CACHE_TTL_MS: u64 = 300;
+/// Upper bound on the configured lifetime, so enumerations can never get badly stale
+pub const MAX_CAPTURE_CACHE_TTL_MS: u64 = 2000;
+
+/// Cache lifetime from GOOSE_SCREEN_CACHE_MS, where 0 disables caching.
+pub fn capture_cache_ttl_from_env() -> Duration {
+    parse_capture_cache_ttl(std::env::var("GOOSE_SCREEN_CACHE_MS").ok().as_deref())
+}
+
+fn parse_capture_cache_ttl(value: Option<&str>) -> Duration {
+    let millis = value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CAPTURE_CACHE_TTL_MS)
+        .min(MAX_CAPTURE_CACHE_TTL_MS);
+    Duration::from_millis(millis)
+}
+
+/// A single value reused until it is older than `ttl`
+#[derive(Debug)]
+pub struct TimedCache<T> {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, T)>>,
+}
+
+impl<T: Clone> TimedCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Return the cached value while it is fresh, otherwise load and cache a new one.
+    ///
+    /// Failed loads are not cached.
+    pub fn get_or_load<E>(&self, load: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        if self.ttl.is_zero() {
+            return load();
+        }
+
+        let mut entry = self.entry.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((loaded_at, value)) = entry.as_ref() {
+            if loaded_at.elapsed() < self.ttl {
+                return Ok(value.clone());
+            }
+        }
+
+        let value = load()?;
+        *entry = Some((Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    pub fn invalidate(&self) {
+        *self.entry.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// A window as enumerated by the platform, without its handle. Only this plain data is
+/// cached; capturing looks the window up again by id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowInfo {
+    pub id: u32,
+    pub title: String,
+    pub app_name: String,
+    pub width: u32,
+    pub height: u32,
+    pub minimized: bool,
+}
+
+impl From<&Window> for WindowInfo {
+    fn from(window: &Window) -> Self {
+        Self {
+            id: window.id(),
+            title: window.title().to_string(),
+            app_name: window.app_name().to_string(),
+            width: window.width(),
+            height: window.height(),
+            minimized: window.is_minimized(),
+        }
+    }
+}
+
+/// A monitor's id and position, cached without its handle like `WindowInfo`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonitorInfo {
+    pub id: u32,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl From<&Monitor> for MonitorInfo {
+    fn from(monitor: &Monitor) -> Self {
+        Self {
+            id: monitor.id(),
+            x: monitor.x(),
+            y: monitor.y(),
+        }
+    }
+}
+
+/// Short-lived window and monitor enumerations shared by the screen tools, so a
+/// `list_windows` followed by `screen_capture` only enumerates once.
+#[derive(Debug)]
+pub struct CaptureCache {
+    pub windows: TimedCache<Vec<WindowInfo>>,
+    pub monitors: TimedCache<Vec<MonitorInfo>>,
+}
+
+impl CaptureCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            windows: TimedCache::new(ttl),
+            monitors: TimedCache::new(ttl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_timed_cache_reuses_fresh_values() {
+        let cache = TimedCache::new(Duration::from_secs(60));
+        let loads = Cell::new(0);
+        let load = || -> Result<i32, ()> {
+            loads.set(loads.get() + 1);
+            Ok(loads.get())
+        };
+
+        assert_eq!(cache.get_or_load(load), Ok(1));
+        assert_eq!(cache.get_or_load(load), Ok(1));
+
+        cache.invalidate();
+        assert_eq!(cache.get_or_load(load), Ok(2));
+
+        // Errors are returned but never cached
+        let cache = TimedCache::<i32>::new(Duration::from_secs(60));
+        assert_eq!(cache.get_or_load(|| Err("busy")), Err("busy"));
+        assert_eq!(cache.get_or_load(|| Ok::<_, &str>(7)), Ok(7));
+    }
+
+    #[test]
+    fn test_timed_cache_disabled_or_expired() {
+        let loads = Cell::new(0);
+        let load = || -> Result<i32, ()> {
+            loads.set(loads.get() + 1);
+            Ok(loads.get())
+        };
+
+        let disabled = TimedCache::new(Duration::ZERO);
+        assert_eq!(disabled.get_or_load(load), Ok(1));
+        assert_eq!(disabled.get_or_load(load), Ok(2));
+
+        let short = TimedCache::new(Duration::from_millis(5));
+        assert_eq!(short.get_or_load(load), Ok(3));
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(short.get_or_load(load), Ok(4));
+    }
+
+    #[test]
+    fn test_parse_capture_cache_ttl() {
+        assert_eq!(
+            parse_capture_cache_ttl(None),
+            Duration::from_millis(DEFAULT_CAPTURE_CACHE_TTL_MS)
+        );
+        assert_eq!(parse_capture_cache_ttl(Some("0")), Duration::ZERO);
+        assert_eq!(
+            parse_capture_cache_ttl(Some(" 150 ")),
+            Duration::from_millis(150)
+        );
+        assert_eq!(
+            parse_capture_cache_ttl(Some("60000")),
+            Duration::from_millis(MAX_CAPTURE_CACHE_TTL_MS)
+        );
+        assert_eq!(
+            parse_capture_cache_ttl(Some("soon")),
+            Duration::from_millis(DEFAULT_CAPTURE_CACHE_TTL_MS)
+        );
+    }
+}
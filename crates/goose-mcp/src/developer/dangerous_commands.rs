@@ -0,0 +1,123 @@
+use regex::Regex;
+
+use super::rmcp_developer::env_flag;
+
+/// Shell commands that destroy data or history and are hard to undo
+const DEFAULT_DANGEROUS_PATTERNS: &[&str] = &[
+    // Recursive forced removal, e.g. `rm -rf`, `rm -fr` or `rm -r -f`
+    r"\brm\s+(?:-\S+\s+)*-[a-zA-Z]*(?:[rR][a-zA-Z]*f|f[a-zA-Z]*[rR])",
+    r"\brm\s+(?:-\S+\s+)*-[rR]\s+(?:-\S+\s+)*-f\b",
+    // Git commands that discard uncommitted work or rewrite remote history
+    r"\bgit\s+reset\s+(?:\S+\s+)*--hard\b",
+    r"\bgit\s+clean\s+(?:\S+\s+)*-[a-zA-Z]*f",
+    r"\bgit\s+push\s+(?:\S+\s+)*(?:--force\b|--force-with-lease\b|-f\b)",
+    // Raw disk writes and filesystem creation
+    r"\bdd\s+(?:\S+\s+)*of=",
+    r"\bmkfs(?:\.\w+)?\b",
+    r">\s*/dev/(?:sd|nvme|disk|hd)",
+    r"\bshred\b",
+];
+
+/// Flags shell commands that deserve an explicit confirmation before they run.
+///
+/// Uses the built-in patterns plus any from GOOSE_SHELL_DANGEROUS_PATTERNS, a JSON array of
+/// regexes. Setting GOOSE_SHELL_DANGEROUS_DEFAULTS to "false" drops the built-in patterns.
+#[derive(Debug, Clone)]
+pub struct DangerousCommands {
+    patterns: Vec<Regex>,
+}
+
+impl DangerousCommands {
+    /// Matches `patterns`; invalid patterns are logged and skipped
+    pub fn new<S: AsRef<str>>(patterns: impl IntoIterator<Item = S>) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .filter_map(|pattern| match Regex::new(pattern.as_ref()) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    tracing::warn!(
+                        "Ignoring invalid dangerous command pattern '{}': {}",
+                        pattern.as_ref(),
+                        e
+                    );
+                    None
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn from_env() -> Self {
+        let use_defaults = env_flag("GOOSE_SHELL_DANGEROUS_DEFAULTS", true);
+        let extra: Vec<String> = std::env::var("GOOSE_SHELL_DANGEROUS_PATTERNS")
+            .ok()
+            .and_then(|value| {
+                serde_json::from_str(&value)
+                    .map_err(|e| {
+                        tracing::warn!(
+                            "GOOSE_SHELL_DANGEROUS_PATTERNS is not a JSON array of strings: {}",
+                            e
+                        )
+                    })
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        let defaults = DEFAULT_DANGEROUS_PATTERNS
+            .iter()
+            .filter(|_| use_defaults)
+            .map(|pattern| pattern.to_string());
+        Self::new(defaults.chain(extra))
+    }
+
+    /// The first pattern `command` matches, if it looks dangerous
+    pub fn classify(&self, command: &str) -> Option<&str> {
+        self.patterns
+            .iter()
+            .find(|pattern| pattern.is_match(command))
+            .map(Regex::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_patterns() {
+        let dangerous = DangerousCommands::new(DEFAULT_DANGEROUS_PATTERNS);
+        for command in [
+            "rm -rf build",
+            "rm -v -fr /tmp/x",
+            "rm -r -f target",
+            "git reset --hard HEAD~1",
+            "git clean -fdx",
+            "git push origin main --force",
+            "dd if=image.iso of=/dev/sdb bs=4M",
+            "mkfs.ext4 /dev/sdb1",
+            "cat zeros > /dev/sda",
+        ] {
+            assert!(dangerous.classify(command).is_some(), "{}", command);
+        }
+        for command in [
+            "rm file.txt",
+            "rm -r empty_dir",
+            "git reset HEAD file.txt",
+            "git push origin main",
+            "cargo build --release",
+            "ls -rf",
+        ] {
+            assert!(dangerous.classify(command).is_none(), "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_custom_and_invalid_patterns() {
+        let dangerous = DangerousCommands::new([r"\bkubectl\s+delete\b", "(unclosed"]);
+        assert_eq!(
+            dangerous.classify("kubectl delete pod web"),
+            Some(r"\bkubectl\s+delete\b")
+        );
+        assert!(dangerous.classify("rm -rf build").is_none());
+    }
+}
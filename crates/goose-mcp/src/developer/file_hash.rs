@@ -0,0 +1,103 @@
+use md5::Md5;
+use rmcp::model::{ErrorCode, ErrorData};
+use sha2::{Digest, Sha256};
+use std::{fs::File, io::Read, path::Path};
+
+pub const DEFAULT_HASH_ALGORITHM: &str = "sha256";
+pub const SUPPORTED_HASH_ALGORITHMS: &[&str] = &["sha256", "md5", "blake3"];
+pub const MAX_HASH_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB
+
+// Feed the file to the hasher in chunks so large files never sit in memory
+fn stream_into(mut file: File, mut update: impl FnMut(&[u8])) -> Result<(), ErrorData> {
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to read file: {}", e),
+                None,
+            )
+        })?;
+        if read == 0 {
+            return Ok(());
+        }
+        update(&buffer[..read]);
+    }
+}
+
+/// Compute the lowercase hex digest of a file with one of the supported algorithms
+pub fn hash_file(path: &Path, algorithm: &str) -> Result<String, ErrorData> {
+    if !SUPPORTED_HASH_ALGORITHMS.contains(&algorithm) {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "Unsupported hash algorithm '{}'. Supported algorithms are: {}",
+                algorithm,
+                SUPPORTED_HASH_ALGORITHMS.join(", ")
+            ),
+            None,
+        ));
+    }
+
+    if !path.is_file() {
+        return Err(ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!(
+                "The path '{}' does not exist or is not a file.",
+                path.display()
+            ),
+            None,
+        ));
+    }
+
+    let file = File::open(path).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to open file: {}", e),
+            None,
+        )
+    })?;
+
+    let file_size = file
+        .metadata()
+        .map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to get file metadata: {}", e),
+                None,
+            )
+        })?
+        .len();
+
+    if file_size > MAX_HASH_FILE_SIZE {
+        return Err(ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!(
+                "File '{}' is too large ({:.2}MB). Maximum size is 10MB.",
+                path.display(),
+                file_size as f64 / (1024.0 * 1024.0)
+            ),
+            None,
+        ));
+    }
+
+    match algorithm {
+        "md5" => {
+            let mut hasher = Md5::new();
+            stream_into(file, |chunk| hasher.update(chunk))?;
+            Ok(format!("{:x}", hasher.finalize()))
+        }
+        "blake3" => {
+            let mut hasher = blake3::Hasher::new();
+            stream_into(file, |chunk| {
+                hasher.update(chunk);
+            })?;
+            Ok(hasher.finalize().to_hex().to_string())
+        }
+        _ => {
+            let mut hasher = Sha256::new();
+            stream_into(file, |chunk| hasher.update(chunk))?;
+            Ok(format!("{:x}", hasher.finalize()))
+        }
+    }
+}
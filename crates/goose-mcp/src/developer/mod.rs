@@ -1,7 +1,17 @@
+mod capture_cache;
+mod dangerous_commands;
 mod editor_models;
+mod file_hash;
 mod goose_hints;
 mod lang;
+mod patch;
+mod redaction;
 mod shell;
+mod shell_history;
+mod shell_output;
+mod structured;
+mod tail;
 mod text_editor;
+mod tool_metrics;
 
 pub mod rmcp_developer;
@@ -0,0 +1,302 @@
+use rmcp::model::{ErrorCode, ErrorData};
+
+use super::text_editor::LineEnding;
+
+const DEV_NULL: &str = "/dev/null";
+
+/// One line of a hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+/// A `@@ -start,count +start,count @@` section of a file's diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    pub header: String,
+    /// 1-indexed line the hunk starts at in the original file, 0 when it was empty
+    pub old_start: usize,
+    pub lines: Vec<HunkLine>,
+    /// Set by a `\ No newline at end of file` marker after the hunk's last new line
+    pub no_final_newline: bool,
+}
+
+impl Hunk {
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+
+    fn new_lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().filter_map(|line| match line {
+            HunkLine::Context(text) | HunkLine::Add(text) => Some(text.as_str()),
+            HunkLine::Remove(_) => None,
+        })
+    }
+}
+
+/// The changes a diff makes to one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch {
+    /// None when the file is created by the patch
+    pub old_path: Option<String>,
+    /// None when the file is deleted by the patch
+    pub new_path: Option<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// The file the patch applies to
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or(DEV_NULL)
+    }
+}
+
+fn invalid_patch(message: String) -> ErrorData {
+    ErrorData::new(ErrorCode::INVALID_PARAMS, message, None)
+}
+
+// Path from a `---`/`+++` header, without a trailing timestamp or the `a/`/`b/` prefix
+fn parse_header_path(header: &str) -> Option<String> {
+    let path = header.split('\t').next().unwrap_or(header).trim();
+    if path == DEV_NULL {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(path.to_string())
+}
+
+// The original start line and the old and new line counts from a
+// `@@ -start,count +start,count @@` header, where a missing count means one line
+fn parse_hunk_header(header: &str) -> Option<(usize, usize, usize)> {
+    let mut ranges = header.strip_prefix("@@ -")?.split_whitespace();
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = parse_range(ranges.next()?)?;
+    let (_, new_count) = parse_range(ranges.next()?.strip_prefix('+')?)?;
+    Some((old_start, old_count, new_count))
+}
+
+/// Parse a unified diff, which may change several files.
+///
+/// Lines outside of file sections, such as `diff --git` and `index` lines, are ignored.
+pub fn parse_patch(patch: &str) -> Result<Vec<FilePatch>, ErrorData> {
+    let mut files: Vec<FilePatch> = Vec::new();
+    let mut lines = patch.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(old_header) = line.strip_prefix("--- ") {
+            let new_header = lines
+                .next()
+                .and_then(|next| next.strip_prefix("+++ "))
+                .ok_or_else(|| invalid_patch(format!("Expected a '+++' line after '{}'", line)))?;
+            files.push(FilePatch {
+                old_path: parse_header_path(old_header),
+                new_path: parse_header_path(new_header),
+                hunks: Vec::new(),
+            });
+        } else if line.starts_with("@@") {
+            let file = files.last_mut().ok_or_else(|| {
+                invalid_patch(format!("Hunk '{}' comes before any file header", line))
+            })?;
+            let (old_start, mut old_remaining, mut new_remaining) = parse_hunk_header(line)
+                .ok_or_else(|| invalid_patch(format!("Invalid hunk header '{}'", line)))?;
+            let mut hunk = Hunk {
+                header: line.to_string(),
+                old_start,
+                lines: Vec::new(),
+                no_final_newline: false,
+            };
+
+            // The header's line counts say where the hunk ends
+            while old_remaining > 0 || new_remaining > 0 {
+                let next = lines.next().ok_or_else(|| {
+                    invalid_patch(format!("Hunk '{}' ends before all its lines", hunk.header))
+                })?;
+                let hunk_line = match next.chars().next() {
+                    // Some tools drop the space on empty context lines
+                    Some(' ') | None => HunkLine::Context(next.get(1..).unwrap_or("").to_string()),
+                    Some('-') => HunkLine::Remove(next[1..].to_string()),
+                    Some('+') => HunkLine::Add(next[1..].to_string()),
+                    Some('\\') => continue,
+                    _ => {
+                        return Err(invalid_patch(format!(
+                            "Unexpected line '{}' in hunk '{}'",
+                            next, hunk.header
+                        )))
+                    }
+                };
+                if !matches!(hunk_line, HunkLine::Add(_)) {
+                    old_remaining = old_remaining.saturating_sub(1);
+                }
+                if !matches!(hunk_line, HunkLine::Remove(_)) {
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+                hunk.lines.push(hunk_line);
+            }
+            // A marker after the last line says the file ends without a newline
+            if lines.peek().is_some_and(|next| next.starts_with('\\'))
+                && !matches!(hunk.lines.last(), Some(HunkLine::Remove(_)))
+            {
+                hunk.no_final_newline = true;
+                lines.next();
+            }
+            file.hunks.push(hunk);
+        }
+    }
+
+    if files.is_empty() {
+        return Err(invalid_patch(
+            "The patch does not contain any '---'/'+++' file headers".to_string(),
+        ));
+    }
+    if let Some(file) = files.iter().find(|file| file.hunks.is_empty()) {
+        return Err(invalid_patch(format!(
+            "The patch for '{}' has no hunks",
+            file.path()
+        )));
+    }
+    Ok(files)
+}
+
+// Where `needle` occurs in `lines` at or after `min`, preferring the spot closest to `expected`
+fn find_hunk(lines: &[&str], needle: &[&str], min: usize, expected: usize) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(expected.clamp(min, lines.len()));
+    }
+    let last = lines.len().checked_sub(needle.len())?;
+    if min > last {
+        return None;
+    }
+    let matches_at = |start: usize| lines[start..start + needle.len()] == *needle;
+    let expected = expected.clamp(min, last);
+    (0..=last - min).find_map(|distance| {
+        [expected.checked_sub(distance), Some(expected + distance)]
+            .into_iter()
+            .flatten()
+            .find(|&start| start >= min && start <= last && matches_at(start))
+    })
+}
+
+/// Apply a file's hunks to its contents, in order.
+///
+/// Each hunk is matched on its context and removed lines, looking for the nearest match to
+/// the line numbers in its header so the patch still applies when earlier edits moved
+/// things around. The file's line ending style is kept. Fails naming the first hunk that
+/// could not be matched.
+pub fn apply_hunks(content: &str, hunks: &[Hunk]) -> Result<String, ErrorData> {
+    let line_ending = LineEnding::detect(content).unwrap_or(LineEnding::Lf);
+    let had_final_newline = content.is_empty() || content.ends_with('\n');
+    let original: Vec<&str> = content.lines().collect();
+
+    let mut result: Vec<&str> = Vec::new();
+    let mut consumed = 0;
+    // Shift between the header line numbers and where hunks actually matched
+    let mut offset: isize = 0;
+    let mut no_final_newline = !had_final_newline;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let old_lines = hunk.old_lines();
+        let expected = (hunk.old_start.saturating_sub(1) as isize + offset).max(0) as usize;
+        let start = find_hunk(&original, &old_lines, consumed, expected).ok_or_else(|| {
+            invalid_patch(format!(
+                "Hunk {} ({}) did not apply: its context was not found in the file",
+                index + 1,
+                hunk.header
+            ))
+        })?;
+
+        result.extend(&original[consumed..start]);
+        result.extend(hunk.new_lines());
+        consumed = start + old_lines.len();
+        offset = start as isize - hunk.old_start.saturating_sub(1) as isize;
+        if consumed == original.len() {
+            no_final_newline = hunk.no_final_newline;
+        }
+    }
+    result.extend(&original[consumed..]);
+
+    let mut patched = result.join("\n");
+    if !patched.is_empty() && !no_final_newline {
+        patched.push('\n');
+    }
+    Ok(line_ending.apply(&patched))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn one() {}
+-fn two() {}
++fn two() -> u8 { 2 }
+ fn three() {}
+--- /dev/null
++++ b/NOTES.md
+@@ -0,0 +1,2 @@
++# Notes
++Patched
+";
+
+    #[test]
+    fn test_parse_patch_multiple_files() {
+        let files = parse_patch(PATCH).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path(), "src/lib.rs");
+        assert_eq!(files[0].hunks[0].old_start, 1);
+        assert_eq!(files[0].hunks[0].lines.len(), 4);
+        assert_eq!(files[1].old_path, None);
+        assert_eq!(files[1].path(), "NOTES.md");
+
+        assert!(parse_patch("just some text").is_err());
+    }
+
+    #[test]
+    fn test_apply_hunks_with_moved_context() {
+        let files = parse_patch(PATCH).unwrap();
+        // Two lines were added above the hunk since the diff was made
+        let content = "// header\n\nfn one() {}\nfn two() {}\nfn three() {}\n";
+        assert_eq!(
+            apply_hunks(content, &files[0].hunks).unwrap(),
+            "// header\n\nfn one() {}\nfn two() -> u8 { 2 }\nfn three() {}\n"
+        );
+        assert_eq!(
+            apply_hunks("", &files[1].hunks).unwrap(),
+            "# Notes\nPatched\n"
+        );
+
+        let crlf = "fn one() {}\r\nfn two() {}\r\nfn three() {}\r\n";
+        assert_eq!(
+            apply_hunks(crlf, &files[0].hunks).unwrap(),
+            "fn one() {}\r\nfn two() -> u8 { 2 }\r\nfn three() {}\r\n"
+        );
+
+        let err = apply_hunks("fn one() {}\nfn four() {}\n", &files[0].hunks).unwrap_err();
+        assert!(err
+            .message
+            .contains("Hunk 1 (@@ -1,3 +1,3 @@) did not apply"));
+    }
+}
@@ -95,13 +95,3 @@ pub fn is_absolute_path(path_str: &str) -> bool {
         path_str.starts_with('/')
     }
 }
-
-pub fn normalize_line_endings(text: &str) -> String {
-    if cfg!(windows) {
-        // Ensure CRLF line endings on Windows
-        text.replace("\r\n", "\n").replace("\n", "\r\n")
-    } else {
-        // Ensure LF line endings on Unix
-        text.replace("\r\n", "\n")
-    }
-}
@@ -0,0 +1,102 @@
+use rmcp::model::{ErrorCode, ErrorData};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Number of executed shell commands remembered for `rerun`
+pub const MAX_SHELL_HISTORY: usize = 50;
+
+/// Recently executed shell commands, most recent last, so they can be rerun without
+/// being retyped. It is kept in memory by the developer server, so each session starts
+/// with an empty history that is dropped when the session ends.
+#[derive(Debug)]
+pub struct ShellHistory {
+    capacity: usize,
+    commands: Mutex<VecDeque<String>>,
+}
+
+impl ShellHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            commands: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record an executed command, dropping the oldest once the history is full
+    pub fn record(&self, command: &str) {
+        let mut commands = self.commands.lock().unwrap_or_else(|e| e.into_inner());
+        if self.capacity == 0 {
+            return;
+        }
+        while commands.len() >= self.capacity {
+            commands.pop_front();
+        }
+        commands.push_back(command.to_string());
+    }
+
+    /// The command selected by `rerun`: `last`, or how many commands back to go, where
+    /// `1` is the most recent command
+    pub fn resolve(&self, selector: &str) -> Result<String, ErrorData> {
+        let commands = self.commands.lock().unwrap_or_else(|e| e.into_inner());
+        let selector = selector.trim();
+        let back = if selector.eq_ignore_ascii_case("last") {
+            1
+        } else {
+            selector.parse::<usize>().map_err(|_| {
+                ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "Invalid rerun '{}'. Use 'last' or a number of commands back, where 1 is the most recent.",
+                        selector
+                    ),
+                    None,
+                )
+            })?
+        };
+
+        if back == 0 || back > commands.len() {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!(
+                    "No command to rerun at '{}': the shell history has {} command(s).",
+                    selector,
+                    commands.len()
+                ),
+                None,
+            ));
+        }
+        Ok(commands[commands.len() - back].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_last_and_by_index() {
+        let history = ShellHistory::new(MAX_SHELL_HISTORY);
+        assert!(history.resolve("last").is_err());
+
+        history.record("cargo build");
+        history.record("cargo test -p goose-mcp");
+
+        assert_eq!(history.resolve("last").unwrap(), "cargo test -p goose-mcp");
+        assert_eq!(history.resolve("1").unwrap(), "cargo test -p goose-mcp");
+        assert_eq!(history.resolve("2").unwrap(), "cargo build");
+        assert!(history.resolve("0").is_err());
+        assert!(history.resolve("3").is_err());
+        assert!(history.resolve("previous").is_err());
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let history = ShellHistory::new(2);
+        history.record("one");
+        history.record("two");
+        history.record("three");
+
+        assert_eq!(history.resolve("2").unwrap(), "two");
+        assert!(history.resolve("3").is_err());
+    }
+}
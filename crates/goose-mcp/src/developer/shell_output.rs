@@ -0,0 +1,307 @@
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Bytes of an unterminated line buffered before they are streamed anyway
+pub const SHELL_CHUNK_MAX_BYTES: usize = 4096;
+/// How long an unterminated line may sit in the buffer before it is streamed
+pub const SHELL_CHUNK_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How a chunk of shell output ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkEnd {
+    /// A complete line, ended by `\n` or `\r\n`
+    Newline,
+    /// A line ended by a lone `\r`, which later output overwrites (e.g. a progress bar)
+    CarriageReturn,
+    /// Part of a line, flushed because it grew too long or no more output arrived in time
+    Partial,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputChunk {
+    pub text: String,
+    pub end: ChunkEnd,
+}
+
+/// Splits a command's output into chunks on `\n` and `\r`, flushing unterminated output
+/// on a size or time threshold so progress indicators that never print a newline still
+/// stream while the command runs.
+pub struct ChunkReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    eof: bool,
+    max_bytes: usize,
+    flush_interval: Duration,
+}
+
+impl<R: AsyncRead + Unpin> ChunkReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_thresholds(reader, SHELL_CHUNK_MAX_BYTES, SHELL_CHUNK_FLUSH_INTERVAL)
+    }
+
+    pub fn with_thresholds(reader: R, max_bytes: usize, flush_interval: Duration) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            eof: false,
+            max_bytes,
+            flush_interval,
+        }
+    }
+
+    /// The next chunk of output, or None once the output has ended
+    pub async fn next_chunk(&mut self) -> std::io::Result<Option<OutputChunk>> {
+        let mut read_buf = [0u8; 4096];
+        loop {
+            if let Some(chunk) = self.take_terminated() {
+                return Ok(Some(chunk));
+            }
+            if self.buffer.len() >= self.max_bytes {
+                return Ok(Some(self.take_partial()));
+            }
+            if self.eof {
+                return Ok((!self.buffer.is_empty()).then(|| self.take_partial()));
+            }
+
+            let read = if self.buffer.is_empty() {
+                self.reader.read(&mut read_buf).await?
+            } else {
+                match tokio::time::timeout(self.flush_interval, self.reader.read(&mut read_buf))
+                    .await
+                {
+                    Ok(read) => read?,
+                    Err(_) => return Ok(Some(self.take_partial())),
+                }
+            };
+            if read == 0 {
+                self.eof = true;
+            } else {
+                self.buffer.extend_from_slice(&read_buf[..read]);
+            }
+        }
+    }
+
+    fn take_terminated(&mut self) -> Option<OutputChunk> {
+        let position = self
+            .buffer
+            .iter()
+            .position(|b| *b == b'\n' || *b == b'\r')?;
+        let (end, consumed) = if self.buffer[position] == b'\n' {
+            (ChunkEnd::Newline, position + 1)
+        } else {
+            match self.buffer.get(position + 1) {
+                Some(b'\n') => (ChunkEnd::Newline, position + 2),
+                Some(_) => (ChunkEnd::CarriageReturn, position + 1),
+                // Wait to see whether this `\r` starts a `\r\n`
+                None if !self.eof => return None,
+                None => (ChunkEnd::CarriageReturn, position + 1),
+            }
+        };
+
+        let chunk: Vec<u8> = self.buffer.drain(..consumed).collect();
+        Some(OutputChunk {
+            text: String::from_utf8_lossy(&chunk[..position]).into_owned(),
+            end,
+        })
+    }
+
+    fn take_partial(&mut self) -> OutputChunk {
+        if self.buffer.last() == Some(&b'\r') {
+            let chunk: Vec<u8> = self.buffer.drain(..).collect();
+            return OutputChunk {
+                text: String::from_utf8_lossy(&chunk[..chunk.len() - 1]).into_owned(),
+                end: ChunkEnd::CarriageReturn,
+            };
+        }
+
+        // Keep a multi-byte character split across reads for the next chunk
+        let take = match std::str::from_utf8(&self.buffer) {
+            Err(e) if e.error_len().is_none() && e.valid_up_to() > 0 && !self.eof => {
+                e.valid_up_to()
+            }
+            _ => self.buffer.len(),
+        };
+        let chunk: Vec<u8> = self.buffer.drain(..take).collect();
+        OutputChunk {
+            text: String::from_utf8_lossy(&chunk).into_owned(),
+            end: ChunkEnd::Partial,
+        }
+    }
+}
+
+/// The line a stream is currently writing, assembled from chunks the way a terminal would
+/// show it: text after a `\r` replaces the line instead of adding to it.
+#[derive(Debug, Default)]
+pub struct OutputLine {
+    text: String,
+    overwrite_pending: bool,
+}
+
+impl OutputLine {
+    /// Apply a chunk, appending the line to `combined` once it is complete
+    pub fn apply(&mut self, chunk: &OutputChunk, combined: &mut String) {
+        if self.overwrite_pending && !chunk.text.is_empty() {
+            self.text.clear();
+            self.overwrite_pending = false;
+        }
+        self.text.push_str(&chunk.text);
+
+        match chunk.end {
+            ChunkEnd::Newline => {
+                combined.push_str(&self.text);
+                combined.push('\n');
+                self.text.clear();
+                self.overwrite_pending = false;
+            }
+            ChunkEnd::CarriageReturn => self.overwrite_pending = true,
+            ChunkEnd::Partial => {}
+        }
+    }
+
+    /// Append whatever is left once the stream has ended
+    pub fn finish(self, combined: &mut String) {
+        if !self.text.is_empty() {
+            combined.push_str(&self.text);
+            combined.push('\n');
+        }
+    }
+}
+
+/// A command's output as a terminal would show it, along with what each stream wrote
+#[derive(Debug, Default)]
+pub struct ShellOutput {
+    pub combined: String,
+    pub stdout: String,
+    pub stderr: String,
+    stdout_line: OutputLine,
+    stderr_line: OutputLine,
+}
+
+impl ShellOutput {
+    /// Apply a chunk read from `stream_type`, either `stdout` or `stderr`
+    pub fn apply(&mut self, stream_type: &str, chunk: &OutputChunk) {
+        let start = self.combined.len();
+        let (line, output) = if stream_type == "stdout" {
+            (&mut self.stdout_line, &mut self.stdout)
+        } else {
+            (&mut self.stderr_line, &mut self.stderr)
+        };
+        line.apply(chunk, &mut self.combined);
+        output.push_str(&self.combined[start..]);
+    }
+
+    /// Append whatever is left on either stream once both have ended
+    pub fn finish(&mut self) {
+        let start = self.combined.len();
+        std::mem::take(&mut self.stdout_line).finish(&mut self.combined);
+        self.stdout.push_str(&self.combined[start..]);
+
+        let start = self.combined.len();
+        std::mem::take(&mut self.stderr_line).finish(&mut self.combined);
+        self.stderr.push_str(&self.combined[start..]);
+    }
+
+    /// Add a note from goose itself, shown with the command's errors
+    pub fn push_note(&mut self, note: &str) {
+        self.combined.push_str(note);
+        self.stderr.push_str(note);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn chunks(input: &[u8]) -> Vec<OutputChunk> {
+        let mut reader = ChunkReader::new(input);
+        let mut chunks = Vec::new();
+        while let Some(chunk) = reader.next_chunk().await.unwrap() {
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
+    fn chunk(text: &str, end: ChunkEnd) -> OutputChunk {
+        OutputChunk {
+            text: text.to_string(),
+            end,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_splits_on_newlines_and_carriage_returns() {
+        assert_eq!(
+            chunks(b"start\r\n10%\r100%\ndone").await,
+            vec![
+                chunk("start", ChunkEnd::Newline),
+                chunk("10%", ChunkEnd::CarriageReturn),
+                chunk("100%", ChunkEnd::Newline),
+                chunk("done", ChunkEnd::Partial),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flushes_long_and_stalled_lines() {
+        // A long line is streamed once it fills the buffer, without waiting for more
+        let (mut writer, pipe) = tokio::io::duplex(64);
+        let mut reader = ChunkReader::with_thresholds(pipe, 4, Duration::from_secs(60));
+        tokio::io::AsyncWriteExt::write_all(&mut writer, b"abcdefgh")
+            .await
+            .unwrap();
+        assert_eq!(
+            reader.next_chunk().await.unwrap(),
+            Some(chunk("abcdefgh", ChunkEnd::Partial))
+        );
+
+        // Output that stops without a newline is streamed once the flush interval passes
+        let (mut writer, pipe) = tokio::io::duplex(64);
+        let mut reader = ChunkReader::with_thresholds(pipe, 4096, Duration::from_millis(20));
+        tokio::io::AsyncWriteExt::write_all(&mut writer, b"Downloading 5")
+            .await
+            .unwrap();
+        assert_eq!(
+            reader.next_chunk().await.unwrap(),
+            Some(chunk("Downloading 5", ChunkEnd::Partial))
+        );
+        drop(writer);
+        assert_eq!(reader.next_chunk().await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_output_line_keeps_final_progress_state() {
+        let mut combined = String::new();
+        let mut line = OutputLine::default();
+        for chunk in [
+            chunk("Downloading 5", ChunkEnd::Partial),
+            chunk("0%", ChunkEnd::CarriageReturn),
+            chunk("Downloading 100%", ChunkEnd::Newline),
+            chunk("Installing 1/2", ChunkEnd::CarriageReturn),
+            chunk("Installing 2/2", ChunkEnd::CarriageReturn),
+        ] {
+            line.apply(&chunk, &mut combined);
+        }
+        line.finish(&mut combined);
+
+        assert_eq!(combined, "Downloading 100%\nInstalling 2/2\n");
+    }
+
+    #[test]
+    fn test_shell_output_keeps_streams_apart() {
+        let mut output = ShellOutput::default();
+        output.apply("stdout", &chunk("building", ChunkEnd::Newline));
+        output.apply("stderr", &chunk("warning: unused", ChunkEnd::Newline));
+        output.apply("stdout", &chunk("50%", ChunkEnd::CarriageReturn));
+        output.apply("stdout", &chunk("100%", ChunkEnd::Partial));
+        output.apply("stderr", &chunk("error: failed", ChunkEnd::Partial));
+        output.finish();
+        output.push_note("[hint]\n");
+
+        assert_eq!(
+            output.combined,
+            "building\nwarning: unused\n100%\nerror: failed\n[hint]\n"
+        );
+        assert_eq!(output.stdout, "building\n100%\n");
+        assert_eq!(output.stderr, "warning: unused\nerror: failed\n[hint]\n");
+    }
+}
@@ -0,0 +1,152 @@
+use serde::Deserialize;
+use std::path::Path;
+
+/// Structured file formats the text_editor can pretty-print and validate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuredFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl StructuredFormat {
+    /// Detect the format from a file's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+        }
+    }
+}
+
+/// Why a structured file failed to parse, with the 1-indexed position when known
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseFailure {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl ParseFailure {
+    fn new(message: impl ToString, line: Option<usize>, column: Option<usize>) -> Self {
+        Self {
+            message: message.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+// Convert a byte offset into a 1-indexed line and column
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before, |newline| &before[newline + 1..])
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// Parse `content` as `format` and render it back in a canonical, pretty-printed form
+pub fn pretty_print(format: StructuredFormat, content: &str) -> Result<String, ParseFailure> {
+    match format {
+        StructuredFormat::Json => {
+            let value: serde_json::Value = serde_json::from_str(content)
+                .map_err(|e| ParseFailure::new(&e, Some(e.line()), Some(e.column())))?;
+            serde_json::to_string_pretty(&value).map_err(|e| ParseFailure::new(e, None, None))
+        }
+        StructuredFormat::Yaml => {
+            // Files may hold several documents separated by `---`
+            let mut documents = Vec::new();
+            for document in serde_yaml::Deserializer::from_str(content) {
+                let value = serde_yaml::Value::deserialize(document).map_err(|e| {
+                    let location = e.location();
+                    ParseFailure::new(
+                        &e,
+                        location.as_ref().map(|l| l.line()),
+                        location.as_ref().map(|l| l.column()),
+                    )
+                })?;
+                documents.push(
+                    serde_yaml::to_string(&value).map_err(|e| ParseFailure::new(e, None, None))?,
+                );
+            }
+            Ok(documents.join("---\n"))
+        }
+        StructuredFormat::Toml => {
+            let value: toml::Table = toml::from_str(content).map_err(|e| {
+                let position = e.span().map(|span| line_and_column(content, span.start));
+                ParseFailure::new(
+                    e.message(),
+                    position.map(|(line, _)| line),
+                    position.map(|(_, column)| column),
+                )
+            })?;
+            toml::to_string_pretty(&value).map_err(|e| ParseFailure::new(e, None, None))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_format_by_extension() {
+        assert_eq!(
+            StructuredFormat::from_path(Path::new("package.json")),
+            Some(StructuredFormat::Json)
+        );
+        assert_eq!(
+            StructuredFormat::from_path(Path::new("ci.YML")),
+            Some(StructuredFormat::Yaml)
+        );
+        assert_eq!(
+            StructuredFormat::from_path(Path::new("Cargo.toml")),
+            Some(StructuredFormat::Toml)
+        );
+        assert_eq!(StructuredFormat::from_path(Path::new("main.rs")), None);
+    }
+
+    #[test]
+    fn test_pretty_print_valid_files() {
+        assert_eq!(
+            pretty_print(StructuredFormat::Json, r#"{"a":[1,2]}"#).unwrap(),
+            "{\n  \"a\": [\n    1,\n    2\n  ]\n}"
+        );
+        assert_eq!(
+            pretty_print(StructuredFormat::Yaml, "a:   1\n---\nb: [x, y]\n").unwrap(),
+            "a: 1\n---\nb:\n- x\n- y\n"
+        );
+        assert_eq!(
+            pretty_print(StructuredFormat::Toml, "[package]\nname=\"demo\"\n").unwrap(),
+            "[package]\nname = \"demo\"\n"
+        );
+    }
+
+    #[test]
+    fn test_pretty_print_reports_position() {
+        let failure = pretty_print(StructuredFormat::Json, "{\n  \"a\": 1,\n}").unwrap_err();
+        assert_eq!((failure.line, failure.column), (Some(3), Some(1)));
+
+        let failure = pretty_print(StructuredFormat::Yaml, "a: 1\nb: [unclosed\n").unwrap_err();
+        assert!(failure.line.is_some());
+
+        let failure = pretty_print(StructuredFormat::Toml, "a = 1\nb = \n").unwrap_err();
+        assert_eq!(failure.line, Some(2));
+    }
+}
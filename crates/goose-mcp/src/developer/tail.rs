@@ -0,0 +1,172 @@
+use rmcp::model::{ErrorCode, ErrorData};
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+pub const DEFAULT_TAIL_LINES: usize = 20;
+pub const MAX_TAIL_LINES: usize = 10_000;
+/// Most bytes returned from the end of a file, however few lines they hold
+pub const MAX_TAIL_BYTES: usize = 400_000; // 400KB
+
+const CHUNK_SIZE: u64 = 64 * 1024;
+
+/// The end of a file, read backwards from its last byte
+#[derive(Debug, PartialEq, Eq)]
+pub struct Tail {
+    /// Byte offset the returned text starts at
+    pub start: u64,
+    /// The file's size when it was read
+    pub file_size: u64,
+    pub text: String,
+    /// Number of lines in `text`
+    pub line_count: usize,
+    /// Set when the lines held more than MAX_TAIL_BYTES and only their end was kept
+    pub cut: bool,
+}
+
+fn read_error(e: std::io::Error) -> ErrorData {
+    ErrorData::new(
+        ErrorCode::INTERNAL_ERROR,
+        format!("Failed to read file: {}", e),
+        None,
+    )
+}
+
+// Where the last `lines` lines of `bytes` start, ignoring a final line break
+fn last_lines_start(bytes: &[u8], lines: usize) -> Option<usize> {
+    let body = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    body.iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, &byte)| byte == b'\n')
+        .nth(lines - 1)
+        .map(|(index, _)| index + 1)
+}
+
+/// Read the last `lines` lines of `reader`, which holds `file_size` bytes, without reading
+/// anything before `floor`. Chunks are read from the end until enough line breaks are
+/// found, so the cost depends on the lines returned rather than the size of the file.
+pub fn tail_lines<R: Read + Seek>(
+    reader: &mut R,
+    file_size: u64,
+    lines: usize,
+    floor: u64,
+) -> Result<Tail, ErrorData> {
+    let mut start = file_size;
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut line_start = None;
+
+    while start > floor && bytes.len() < MAX_TAIL_BYTES {
+        let chunk_size = CHUNK_SIZE.min(start - floor);
+        start -= chunk_size;
+        let mut chunk = Vec::with_capacity(chunk_size as usize);
+        reader
+            .seek(SeekFrom::Start(start))
+            .and_then(|_| reader.by_ref().take(chunk_size).read_to_end(&mut chunk))
+            .map_err(read_error)?;
+        bytes.splice(0..0, chunk);
+
+        line_start = last_lines_start(&bytes, lines);
+        if line_start.is_some() {
+            break;
+        }
+    }
+
+    let mut skip = line_start.unwrap_or(0);
+    let cut = bytes.len() - skip > MAX_TAIL_BYTES;
+    if cut {
+        skip = bytes.len() - MAX_TAIL_BYTES;
+    }
+    let text = String::from_utf8_lossy(&bytes[skip..]).into_owned();
+    Ok(Tail {
+        start: start + skip as u64,
+        file_size,
+        line_count: text.lines().count(),
+        text,
+        cut,
+    })
+}
+
+/// Read the last `lines` lines of the file at `path`.
+///
+/// With `since_size`, only what was written after that many bytes is read, so repeated
+/// calls passing the previous size return just the new lines. A file smaller than
+/// `since_size` was truncated or rotated and is read from its start.
+pub fn tail_file(path: &Path, lines: usize, since_size: Option<u64>) -> Result<Tail, ErrorData> {
+    if lines == 0 || lines > MAX_TAIL_LINES {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("lines must be between 1 and {}", MAX_TAIL_LINES),
+            None,
+        ));
+    }
+    if !path.is_file() {
+        return Err(ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!(
+                "The path '{}' does not exist or is not a file.",
+                path.display()
+            ),
+            None,
+        ));
+    }
+
+    let mut file = File::open(path).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to open file: {}", e),
+            None,
+        )
+    })?;
+    let file_size = file.metadata().map_err(read_error)?.len();
+    let floor = since_size.filter(|&size| size <= file_size).unwrap_or(0);
+    tail_lines(&mut file, file_size, lines, floor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn tail(content: &str, lines: usize, floor: u64) -> Tail {
+        tail_lines(
+            &mut Cursor::new(content.as_bytes()),
+            content.len() as u64,
+            lines,
+            floor,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        let content = "one\ntwo\nthree\nfour\n";
+        let last = tail(content, 2, 0);
+        assert_eq!(last.text, "three\nfour\n");
+        assert_eq!(last.start, 8);
+        assert_eq!(last.line_count, 2);
+
+        assert_eq!(tail(content, 10, 0).text, content);
+        assert_eq!(tail("one\ntwo", 1, 0).text, "two");
+        assert_eq!(tail(content, 10, 14).text, "four\n");
+        assert_eq!(tail(content, 10, 19).text, "");
+        assert_eq!(tail("", 5, 0).line_count, 0);
+    }
+
+    #[test]
+    fn test_tail_lines_across_chunks() {
+        let content: String = (0..50_000).map(|i| format!("line {}\n", i)).collect();
+        let last = tail(&content, 3, 0);
+        assert_eq!(last.text, "line 49997\nline 49998\nline 49999\n");
+        assert!(!last.cut);
+
+        // A single line longer than the cap keeps only its end
+        let long = "x".repeat(MAX_TAIL_BYTES + 10);
+        let last = tail(&long, 1, 0);
+        assert!(last.cut);
+        assert_eq!(last.text.len(), MAX_TAIL_BYTES);
+        assert_eq!(last.start, 10);
+    }
+}
@@ -2,19 +2,239 @@ use anyhow::Result;
 use indoc::formatdoc;
 use std::{
     fs::File,
-    io::Read,
+    io::{Read, Seek, SeekFrom},
     path::{Path, PathBuf},
 };
 use url::Url;
 
 use rmcp::model::{Content, ErrorCode, ErrorData, Role};
+use rmcp::schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use super::editor_models::EditorModel;
 use super::lang;
-use super::shell::normalize_line_endings;
+use super::structured::{pretty_print, ParseFailure, StructuredFormat};
 
 // Constants
 pub const LINE_READ_LIMIT: usize = 2000;
+pub const MAX_VIEW_FILE_SIZE: u64 = 400 * 1024; // 400KB
+/// Hard ceiling for the view size limit when it is raised with `max_size`
+pub const MAX_VIEW_FILE_SIZE_CEILING: u64 = 5 * 1024 * 1024; // 5MB
+const BINARY_SNIFF_LEN: usize = 8192;
+/// Default number of undo versions kept per file, see `max_undo_versions`
+pub const DEFAULT_MAX_UNDO_VERSIONS: usize = 20;
+/// UTF-8 byte order mark, written at the start of files by some Windows editors
+const UTF8_BOM: char = '\u{FEFF}';
+
+/// Split a leading byte order mark off `text`, returning the rest and whether it had one
+pub fn split_bom(text: &str) -> (&str, bool) {
+    match text.strip_prefix(UTF8_BOM) {
+        Some(rest) => (rest, true),
+        None => (text, false),
+    }
+}
+
+/// Heuristic used by most tools: NUL bytes or invalid UTF-8 near the start mean binary
+pub fn looks_binary(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => false,
+        // A multibyte character cut off at the end of the sample is still text
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+// Name common binary formats from their magic numbers
+fn binary_file_kind(sample: &[u8]) -> Option<(&'static str, bool)> {
+    const SIGNATURES: &[(&[u8], &str, bool)] = &[
+        (b"\x89PNG\r\n\x1a\n", "PNG image", true),
+        (b"\xff\xd8\xff", "JPEG image", true),
+        (b"GIF8", "GIF image", true),
+        (b"BM", "BMP image", true),
+        (b"%PDF", "PDF document", false),
+        (b"PK\x03\x04", "ZIP archive", false),
+        (b"\x1f\x8b", "gzip archive", false),
+        (b"\x7fELF", "ELF executable", false),
+        (b"\0asm", "WebAssembly module", false),
+        (b"SQLite format 3\0", "SQLite database", false),
+    ];
+    if sample.len() >= 12 && &sample[..4] == b"RIFF" && &sample[8..12] == b"WEBP" {
+        return Some(("WebP image", true));
+    }
+    SIGNATURES
+        .iter()
+        .find(|(magic, _, _)| sample.starts_with(magic))
+        .map(|(_, kind, is_image)| (*kind, *is_image))
+}
+
+/// Read a file that is about to be edited as text. Invalid UTF-8 is refused rather than
+/// decoded lossily, since writing the lossy text back would replace the invalid bytes, and
+/// with `protect_binary` so are files that look binary.
+pub fn read_editable_text(path: &Path, protect_binary: bool) -> Result<String, ErrorData> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to read file: {}", e),
+            None,
+        )
+    })?;
+
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_LEN)];
+    if protect_binary && looks_binary(sample) {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "{} Editing it as text would corrupt it.",
+                describe_binary_file(path, sample, bytes.len() as u64)
+            ),
+            None,
+        ));
+    }
+
+    String::from_utf8(bytes).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "'{}' is not valid UTF-8 (invalid byte at offset {}), so it cannot be edited as text without corrupting it.",
+                path.display(),
+                e.utf8_error().valid_up_to()
+            ),
+            None,
+        )
+    })
+}
+
+/// Whether an existing file at `path` looks binary, judged from its first bytes
+fn existing_file_looks_binary(path: &Path) -> bool {
+    let mut sample = Vec::new();
+    File::open(path)
+        .and_then(|f| f.take(BINARY_SNIFF_LEN as u64).read_to_end(&mut sample))
+        .map(|_| looks_binary(&sample))
+        .unwrap_or(false)
+}
+
+fn describe_binary_file(path: &Path, sample: &[u8], file_size: u64) -> String {
+    let (kind, is_image) = binary_file_kind(sample).unwrap_or(("binary data", false));
+    let mut message = format!(
+        "'{}' is a binary file ({}, {:.2}KB) and cannot be displayed as text.",
+        path.display(),
+        kind,
+        file_size as f64 / 1024.0
+    );
+    if is_image {
+        message.push_str(" Use the image_processor tool to view it.");
+    }
+    message
+}
+
+/// Line ending style used when writing files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn parse(value: &str) -> Result<Self, ErrorData> {
+        match value.to_ascii_lowercase().as_str() {
+            "lf" => Ok(LineEnding::Lf),
+            "crlf" => Ok(LineEnding::CrLf),
+            _ => Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("Unknown line ending '{}', expected 'lf' or 'crlf'", value),
+                None,
+            )),
+        }
+    }
+
+    pub fn platform_default() -> Self {
+        if cfg!(windows) {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// The dominant line ending in `content`, or None if it has no line breaks
+    pub fn detect(content: &str) -> Option<Self> {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+        match (crlf, lf) {
+            (0, 0) => None,
+            (crlf, lf) if crlf > lf => Some(LineEnding::CrLf),
+            _ => Some(LineEnding::Lf),
+        }
+    }
+
+    /// Pick the ending for a write: an explicit override wins, then the style of the
+    /// existing content, then the platform convention
+    pub fn resolve(requested: Option<Self>, existing: Option<&str>) -> Self {
+        requested
+            .or_else(|| existing.and_then(Self::detect))
+            .unwrap_or_else(Self::platform_default)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// Convert every line break in `text` to this style
+    pub fn apply(self, text: &str) -> String {
+        let text = text.replace("\r\n", "\n");
+        match self {
+            LineEnding::Lf => text,
+            LineEnding::CrLf => text.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// How content is normalized before the editor writes it to disk
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Force this line ending instead of keeping the file's own
+    pub line_ending: Option<LineEnding>,
+    /// Strip spaces and tabs from the end of every line
+    pub trim_trailing_whitespace: bool,
+    /// Make sure non-empty files end with a line break
+    pub ensure_final_newline: bool,
+    /// Refuse to write over or edit a file that looks binary
+    pub protect_binary: bool,
+    /// Drop a UTF-8 byte order mark instead of keeping it
+    pub strip_bom: bool,
+}
+
+impl WriteOptions {
+    /// Apply the whitespace options to `text`, writing it out with `line_ending`
+    pub fn finish(&self, text: &str, line_ending: LineEnding) -> String {
+        let mut text = LineEnding::Lf.apply(text);
+        if self.trim_trailing_whitespace {
+            text = text
+                .split('\n')
+                .map(|line| line.trim_end_matches([' ', '\t']))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        if self.ensure_final_newline && !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        line_ending.apply(&text)
+    }
+
+    /// Like `finish`, starting the text with a byte order mark when the file had one
+    pub fn finish_file(&self, text: &str, line_ending: LineEnding, bom: bool) -> String {
+        let text = self.finish(text, line_ending);
+        if bom && !self.strip_bom {
+            format!("{}{}", UTF8_BOM, text)
+        } else {
+            text
+        }
+    }
+}
 
 // Helper method to validate and calculate view range indices
 pub fn calculate_view_range(
@@ -115,10 +335,57 @@ pub fn recommend_read_range(path: &Path, total_lines: usize) -> Result<Vec<Conte
     ), None))
 }
 
+/// Resolves the optional `max_size` override for view, in bytes
+fn view_size_limit(max_size: Option<u64>) -> Result<u64, ErrorData> {
+    match max_size {
+        None => Ok(MAX_VIEW_FILE_SIZE),
+        Some(0) => Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            "max_size must be greater than 0".to_string(),
+            None,
+        )),
+        Some(size) if size > MAX_VIEW_FILE_SIZE_CEILING => Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "max_size cannot exceed {} bytes (5MB)",
+                MAX_VIEW_FILE_SIZE_CEILING
+            ),
+            None,
+        )),
+        Some(size) => Ok(size),
+    }
+}
+
 pub async fn text_editor_view(
     path: &PathBuf,
     view_range: Option<(usize, i64)>,
+    byte_range: Option<(u64, u64)>,
+    pretty: bool,
+    max_size: Option<u64>,
 ) -> Result<Vec<Content>, ErrorData> {
+    let size_limit = view_size_limit(max_size)?;
+    if byte_range.is_some() && (view_range.is_some() || pretty) {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            "byte_range cannot be combined with view_range or pretty".to_string(),
+            None,
+        ));
+    }
+    let structured_format = if pretty {
+        Some(StructuredFormat::from_path(path).ok_or_else(|| {
+            ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!(
+                    "Pretty view is only supported for JSON, YAML and TOML files, not '{}'",
+                    path.display()
+                ),
+                None,
+            )
+        })?)
+    } else {
+        None
+    };
+
     if !path.is_file() {
         return Err(ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
@@ -130,9 +397,7 @@ pub async fn text_editor_view(
         ));
     }
 
-    const MAX_FILE_SIZE: u64 = 400 * 1024; // 400KB
-
-    let f = File::open(path).map_err(|e| {
+    let mut f = File::open(path).map_err(|e| {
         ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
             format!("Failed to open file: {}", e),
@@ -151,20 +416,60 @@ pub async fn text_editor_view(
         })?
         .len();
 
-    if file_size > MAX_FILE_SIZE {
-        return Err(ErrorData::new(
-            ErrorCode::INTERNAL_ERROR,
+    // A byte window is read on its own, without loading or counting the rest of the file
+    if let Some((offset, length)) = byte_range {
+        return view_byte_range(&mut f, path, file_size, offset, length, size_limit);
+    }
+
+    // Check for binary content first so the agent gets a useful answer even for large blobs
+    let mut sample = Vec::new();
+    let sniffed = f
+        .by_ref()
+        .take(BINARY_SNIFF_LEN as u64)
+        .read_to_end(&mut sample);
+    sniffed
+        .and_then(|_| f.seek(SeekFrom::Start(0)))
+        .map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to read file: {}", e),
+                None,
+            )
+        })?;
+    if looks_binary(&sample) {
+        return Ok(vec![Content::text(describe_binary_file(
+            path, &sample, file_size,
+        ))]);
+    }
+
+    if file_size == 0 {
+        return Ok(vec![Content::text(format!(
+            "'{}' is an empty file (0 bytes). Use the `write` command to add initial content.",
+            path.display()
+        ))]);
+    }
+
+    if file_size > size_limit {
+        let message = if max_size.is_some() {
+            format!(
+                "File '{}' is too large ({:.2}KB). It exceeds the requested max_size of {:.2}KB.",
+                path.display(),
+                file_size as f64 / 1024.0,
+                size_limit as f64 / 1024.0
+            )
+        } else {
             format!(
-                "File '{}' is too large ({:.2}KB). Maximum size is 400KB to prevent memory issues.",
+                "File '{}' is too large ({:.2}KB). Maximum size is 400KB to prevent memory issues. \
+                 If you really need the whole file, pass max_size (in bytes, up to 5MB).",
                 path.display(),
                 file_size as f64 / 1024.0
-            ),
-            None,
-        ));
+            )
+        };
+        return Err(ErrorData::new(ErrorCode::INTERNAL_ERROR, message, None));
     }
 
     // Ensure we never read over that limit even if the file is being concurrently mutated
-    let mut f = f.take(MAX_FILE_SIZE);
+    let mut f = f.take(size_limit);
 
     let uri = Url::from_file_path(path)
         .map_err(|_| {
@@ -176,8 +481,8 @@ pub async fn text_editor_view(
         })?
         .to_string();
 
-    let mut content = String::new();
-    f.read_to_string(&mut content).map_err(|e| {
+    let mut bytes = Vec::new();
+    f.read_to_end(&mut bytes).map_err(|e| {
         ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
             format!("Failed to read file: {}", e),
@@ -185,6 +490,46 @@ pub async fn text_editor_view(
         )
     })?;
 
+    // Invalid UTF-8 past the sniffed start is shown lossily, with a warning, since edits
+    // to this file would be refused
+    let (mut content, utf8_warning) = match String::from_utf8(bytes) {
+        Ok(content) => (content, None),
+        Err(e) => {
+            let warning = format!(
+                "Warning: '{}' is not valid UTF-8 (invalid byte at offset {}). Invalid bytes are shown as replacement characters and the file cannot be edited as text.",
+                path.display(),
+                e.utf8_error().valid_up_to()
+            );
+            (
+                String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                Some(warning),
+            )
+        }
+    };
+
+    // The BOM is invisible in most editors, so hide it here too and say it is there
+    let bom_note = match split_bom(&content) {
+        (rest, true) => {
+            content = rest.to_string();
+            Some(format!(
+                "Note: '{}' starts with a UTF-8 byte order mark (BOM), which is not shown. Edits keep it unless strip_bom is set.",
+                path.display()
+            ))
+        }
+        _ => None,
+    };
+
+    if let Some(format) = structured_format {
+        content = match pretty_print(format, &content) {
+            Ok(pretty) => pretty,
+            Err(failure) => {
+                return Ok(vec![Content::text(describe_parse_failure(
+                    path, format, &failure,
+                ))])
+            }
+        };
+    }
+
     let lines: Vec<&str> = content.lines().collect();
     let total_lines = lines.len();
 
@@ -199,22 +544,316 @@ pub async fn text_editor_view(
 
     // The LLM gets just a quick update as we expect the file to view in the status
     // but we send a low priority message for the human
-    Ok(vec![
+    let mut result = vec![
         Content::embedded_text(uri, content).with_audience(vec![Role::Assistant]),
         Content::text(formatted)
             .with_audience(vec![Role::User])
             .with_priority(0.0),
+    ];
+    if let Some(note) = bom_note {
+        result.insert(0, Content::text(note));
+    }
+    if let Some(warning) = utf8_warning {
+        result.insert(0, Content::text(warning));
+    }
+    Ok(result)
+}
+
+/// Read `length` bytes from `offset`, decoded as lossy UTF-8 between markers that say
+/// which bytes are shown
+fn view_byte_range(
+    f: &mut File,
+    path: &Path,
+    file_size: u64,
+    offset: u64,
+    length: u64,
+    size_limit: u64,
+) -> Result<Vec<Content>, ErrorData> {
+    if length == 0 || length > size_limit {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "The byte_range length must be between 1 and {} bytes",
+                size_limit
+            ),
+            None,
+        ));
+    }
+    if offset >= file_size {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "The byte_range offset {} is past the end of '{}' ({} bytes)",
+                offset,
+                path.display(),
+                file_size
+            ),
+            None,
+        ));
+    }
+
+    let mut bytes = Vec::new();
+    f.seek(SeekFrom::Start(offset))
+        .and_then(|_| f.by_ref().take(length).read_to_end(&mut bytes))
+        .map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to read file: {}", e),
+                None,
+            )
+        })?;
+
+    let end = offset + bytes.len() as u64;
+    Ok(vec![Content::text(formatdoc! {"
+        [bytes {offset}-{end} of {file_size} in {path}]
+        {text}
+        [end of bytes {offset}-{end}, {remaining} bytes follow]
+        ",
+        offset = offset,
+        end = end,
+        file_size = file_size,
+        path = path.display(),
+        text = String::from_utf8_lossy(&bytes),
+        remaining = file_size.saturating_sub(end),
+    })])
+}
+
+fn describe_parse_failure(path: &Path, format: StructuredFormat, failure: &ParseFailure) -> String {
+    let position = match (failure.line, failure.column) {
+        (Some(line), Some(column)) => format!(" at line {}, column {}", line, column),
+        (Some(line), None) => format!(" at line {}", line),
+        _ => String::new(),
+    };
+    format!(
+        "'{}' is not valid {}{}: {}",
+        path.display(),
+        format.name(),
+        position,
+        failure.message
+    )
+}
+
+/// Maximum number of entries listed when viewing a directory
+const MAX_DIR_ENTRIES: usize = 1000;
+
+/// List a directory's entries, sorted by name with directories marked by a trailing `/`.
+/// Entries matched by `is_ignored` are left out. With `show_metadata` each entry also shows
+/// its modification time (UTC) and permissions: the mode bits on Unix, or whether it is
+/// read-only elsewhere.
+pub fn text_editor_view_dir(
+    path: &Path,
+    is_ignored: impl Fn(&Path) -> bool,
+    show_metadata: bool,
+) -> Result<Vec<Content>, ErrorData> {
+    let read_error = |e: std::io::Error| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to read directory: {}", e),
+            None,
+        )
+    };
+
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(path).map_err(read_error)? {
+        let entry = entry.map_err(read_error)?;
+        let entry_path = entry.path();
+        if is_ignored(&entry_path) {
+            continue;
+        }
+        let mut name = entry.file_name().to_string_lossy().into_owned();
+        let metadata = entry.metadata().ok();
+        if metadata.as_ref().is_some_and(|m| m.is_dir()) {
+            name.push('/');
+        }
+        let line = match metadata.filter(|_| show_metadata) {
+            Some(metadata) => format!(
+                "{}  {}  {}",
+                format_mtime(&metadata),
+                format_permissions(&metadata),
+                name
+            ),
+            None => name,
+        };
+        entries.push((entry.file_name(), line));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let total = entries.len();
+    let mut listing = entries
+        .into_iter()
+        .take(MAX_DIR_ENTRIES)
+        .map(|(_, line)| line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if total == 0 {
+        listing = "(empty directory)".to_string();
+    } else if total > MAX_DIR_ENTRIES {
+        listing.push_str(&format!(
+            "\n... {} more entries not shown",
+            total - MAX_DIR_ENTRIES
+        ));
+    }
+
+    let text = format!("Directory {}:\n{}\n", path.display(), listing);
+    Ok(vec![
+        Content::text(text.clone()).with_audience(vec![Role::Assistant]),
+        Content::text(text)
+            .with_audience(vec![Role::User])
+            .with_priority(0.0),
     ])
 }
 
-pub async fn text_editor_write(path: &PathBuf, file_text: &str) -> Result<Vec<Content>, ErrorData> {
-    // Normalize line endings based on platform
-    let mut normalized_text = normalize_line_endings(file_text); // Make mutable
+fn format_mtime(metadata: &std::fs::Metadata) -> String {
+    metadata
+        .modified()
+        .map(|modified| {
+            chrono::DateTime::<chrono::Utc>::from(modified)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| "????-??-?? ??:??".to_string())
+}
+
+#[cfg(unix)]
+fn format_permissions(metadata: &std::fs::Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = metadata.permissions().mode();
+    (0..9)
+        .map(|bit| {
+            if mode & (0o400 >> bit) == 0 {
+                '-'
+            } else {
+                ['r', 'w', 'x'][bit % 3]
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn format_permissions(metadata: &std::fs::Metadata) -> String {
+    if metadata.permissions().readonly() {
+        "readonly".to_string()
+    } else {
+        "writable".to_string()
+    }
+}
+
+pub async fn text_editor_stat(path: &PathBuf) -> Result<Vec<Content>, ErrorData> {
+    if !path.is_file() {
+        return Err(ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!(
+                "The path '{}' does not exist or is not a file.",
+                path.display()
+            ),
+            None,
+        ));
+    }
+
+    let f = File::open(path).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to open file: {}", e),
+            None,
+        )
+    })?;
+
+    // Stream the file so large files can be inspected without loading them
+    let mut reader = std::io::BufReader::new(f);
+    let mut buffer = [0u8; 8192];
+    let mut file_size: u64 = 0;
+    let mut newlines: usize = 0;
+    let mut last_byte = None;
+    let mut sample = Vec::new();
+    loop {
+        let read = reader.read(&mut buffer).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to read file: {}", e),
+                None,
+            )
+        })?;
+        if read == 0 {
+            break;
+        }
+        let chunk = &buffer[..read];
+        if sample.len() < BINARY_SNIFF_LEN {
+            sample.extend_from_slice(chunk);
+        }
+        newlines += chunk.iter().filter(|b| **b == b'\n').count();
+        file_size += read as u64;
+        last_byte = chunk.last().copied();
+    }
+    let is_binary = looks_binary(&sample);
+
+    // Match str::lines, where a trailing newline does not start a new line
+    let total_lines = match last_byte {
+        Some(b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    };
+
+    let mut summary = formatdoc! {"
+        path: {path}
+        size: {size} bytes
+        lines: {lines}
+        type: {kind}
+        ",
+        path=path.display(),
+        size=file_size,
+        lines=total_lines,
+        kind=if is_binary { "binary" } else { "text" },
+    };
+    if is_binary {
+        summary.push_str("This file is binary and cannot be viewed with the text editor.\n");
+    } else if file_size > MAX_VIEW_FILE_SIZE {
+        summary.push_str(
+            "This file is larger than the 400KB view limit, search it with the shell or pass max_size to view.\n",
+        );
+    } else if total_lines > LINE_READ_LIMIT {
+        summary.push_str(&format!(
+            "This file has more than {} lines, pass view_range to view it in parts.\n",
+            LINE_READ_LIMIT
+        ));
+    }
+
+    Ok(vec![Content::text(summary)])
+}
+
+pub async fn text_editor_write(
+    path: &PathBuf,
+    file_text: &str,
+    options: WriteOptions,
+) -> Result<Vec<Content>, ErrorData> {
+    if options.protect_binary && existing_file_looks_binary(path) {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "'{}' looks like a binary file, and writing text over it would corrupt it. Delete it first if you mean to replace it.",
+                path.display()
+            ),
+            None,
+        ));
+    }
+
+    // Keep the line endings and byte order mark of a file being overwritten
+    let existing = std::fs::read_to_string(path).ok();
+    let (existing, existing_bom) = match &existing {
+        Some(existing) => {
+            let (text, bom) = split_bom(existing);
+            (Some(text), bom)
+        }
+        None => (None, false),
+    };
+    let (file_text, text_bom) = split_bom(file_text);
+    let line_ending = LineEnding::resolve(options.line_ending, existing);
+    let mut normalized_text = line_ending.apply(file_text); // Make mutable
 
     // Ensure the text ends with a newline
     if !normalized_text.ends_with('\n') {
-        normalized_text.push('\n');
+        normalized_text.push_str(line_ending.as_str());
     }
+    let normalized_text =
+        options.finish_file(&normalized_text, line_ending, existing_bom || text_bom);
 
     // Write to the file
     std::fs::write(path, &normalized_text) // Write the potentially modified text
@@ -250,15 +889,59 @@ pub async fn text_editor_write(path: &PathBuf, file_text: &str) -> Result<Vec<Co
     ])
 }
 
+/// Error for an edit that looks for text in a file with no content at all
+fn empty_file_edit_error(path: &Path) -> ErrorData {
+    ErrorData::new(
+        ErrorCode::INVALID_PARAMS,
+        format!(
+            "'{}' is empty, so 'old_str' cannot match anything. Use the `write` command to add initial content.",
+            path.display()
+        ),
+        None,
+    )
+}
+
+/// Result for an edit that would leave the file exactly as it was. Nothing is written
+/// and no undo history is recorded.
+fn no_changes_made(path: &Path) -> Vec<Content> {
+    vec![
+        Content::text(format!(
+            "No changes made to {}: the replacement leaves the file content unchanged.",
+            path.display()
+        ))
+        .with_audience(vec![Role::Assistant]),
+        Content::text(format!("File {} was not changed", path.display()))
+            .with_audience(vec![Role::User])
+            .with_priority(0.2),
+    ]
+}
+
 #[allow(clippy::too_many_lines)]
+/// Ensure 'old_str' appears exactly once in `content`
+fn ensure_single_occurrence(content: &str, old_str: &str) -> Result<(), ErrorData> {
+    if content.matches(old_str).count() > 1 {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            "'old_str' must appear exactly once in the file, but it appears multiple times"
+                .to_string(),
+            None,
+        ));
+    }
+    if content.matches(old_str).count() == 0 {
+        return Err(ErrorData::new(ErrorCode::INVALID_PARAMS, "'old_str' must appear exactly once in the file, but it does not appear in the file. Make sure the string exactly matches existing file content, including whitespace!".to_string(), None));
+    }
+    Ok(())
+}
+
 pub async fn text_editor_replace(
     path: &PathBuf,
     old_str: &str,
     new_str: &str,
-    editor_model: &Option<EditorModel>,
+    editor_model: Option<&EditorModel>,
     file_history: &std::sync::Arc<
         std::sync::Mutex<std::collections::HashMap<PathBuf, Vec<String>>>,
     >,
+    options: WriteOptions,
 ) -> Result<Vec<Content>, ErrorData> {
     // Check if file exists and is active
     if !path.exists() {
@@ -273,23 +956,38 @@ pub async fn text_editor_replace(
     }
 
     // Read content
-    let content = std::fs::read_to_string(path).map_err(|e| {
-        ErrorData::new(
-            ErrorCode::INTERNAL_ERROR,
-            format!("Failed to read file: {}", e),
-            None,
-        )
-    })?;
+    let original = read_editable_text(path, options.protect_binary)?;
+    let (body, bom) = split_bom(&original);
+    if body.is_empty() && !old_str.is_empty() {
+        return Err(empty_file_edit_error(path));
+    }
 
-    // Check if Editor API is configured and use it as the primary path
-    if let Some(ref editor) = editor_model {
-        // Editor API path - save history then call API directly
-        save_file_history(path, file_history)?;
+    // Match against LF content without the BOM so edits work the same on CRLF and
+    // BOM-prefixed files, then write the result back with the file's own line endings and BOM
+    let line_ending = LineEnding::resolve(options.line_ending, Some(body));
+    let content = LineEnding::Lf.apply(body);
+    let old_str = &LineEnding::Lf.apply(old_str);
+    let new_str = &LineEnding::Lf.apply(new_str);
+
+    // Replacing a string with itself is a no-op, even when the write options would
+    // otherwise reformat the file, once the string is known to be there
+    if old_str == new_str {
+        ensure_single_occurrence(&content, old_str)?;
+        return Ok(no_changes_made(path));
+    }
 
+    // Check if Editor API is configured and use it as the primary path
+    if let Some(editor) = editor_model {
+        // Editor API path - call API directly, then save history before writing
         match editor.edit_code(&content, old_str, new_str).await {
             Ok(updated_content) => {
+                let normalized_content = options.finish_file(&updated_content, line_ending, bom);
+                if normalized_content == original {
+                    return Ok(no_changes_made(path));
+                }
+
                 // Write the updated content directly
-                let normalized_content = normalize_line_endings(&updated_content);
+                save_file_history(path, file_history)?;
                 std::fs::write(path, &normalized_content).map_err(|e| {
                     ErrorData::new(
                         ErrorCode::INTERNAL_ERROR,
@@ -318,24 +1016,16 @@ pub async fn text_editor_replace(
     }
 
     // Traditional string replacement path (original logic)
-    // Ensure 'old_str' appears exactly once
-    if content.matches(old_str).count() > 1 {
-        return Err(ErrorData::new(
-            ErrorCode::INVALID_PARAMS,
-            "'old_str' must appear exactly once in the file, but it appears multiple times"
-                .to_string(),
-            None,
-        ));
-    }
-    if content.matches(old_str).count() == 0 {
-        return Err(ErrorData::new(ErrorCode::INVALID_PARAMS, "'old_str' must appear exactly once in the file, but it does not appear in the file. Make sure the string exactly matches existing file content, including whitespace!".to_string(), None));
+    ensure_single_occurrence(&content, old_str)?;
+
+    let new_content = content.replace(old_str, new_str);
+    let normalized_content = options.finish_file(&new_content, line_ending, bom);
+    if normalized_content == original {
+        return Ok(no_changes_made(path));
     }
 
     // Save history for undo (original behavior - after validation)
     save_file_history(path, file_history)?;
-
-    let new_content = content.replace(old_str, new_str);
-    let normalized_content = normalize_line_endings(&new_content);
     std::fs::write(path, &normalized_content).map_err(|e| {
         ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
@@ -398,15 +1088,28 @@ pub async fn text_editor_replace(
     ])
 }
 
-pub async fn text_editor_insert(
+/// One replacement in an `apply_edits` batch
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TextEdit {
+    /// The text to replace. Must appear exactly once in the file as left by the earlier edits.
+    pub old_str: String,
+    /// The replacement text.
+    pub new_str: String,
+}
+
+/// Apply several replacements to one file as a single edit.
+///
+/// Every edit is checked and applied in memory first, in order, so nothing is written
+/// unless all of them match exactly once. The file is then written once and one history
+/// entry is saved, so a single `undo_edit` reverts the whole batch.
+pub async fn text_editor_apply_edits(
     path: &PathBuf,
-    insert_line_spec: i64,
-    new_str: &str,
+    edits: &[TextEdit],
     file_history: &std::sync::Arc<
         std::sync::Mutex<std::collections::HashMap<PathBuf, Vec<String>>>,
     >,
+    options: WriteOptions,
 ) -> Result<Vec<Content>, ErrorData> {
-    // Check if file exists
     if !path.exists() {
         return Err(ErrorData::new(
             ErrorCode::INVALID_PARAMS,
@@ -418,18 +1121,108 @@ pub async fn text_editor_insert(
         ));
     }
 
-    // Read content
-    let content = std::fs::read_to_string(path).map_err(|e| {
+    if edits.is_empty() {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            "'edits' must contain at least one edit".to_string(),
+            None,
+        ));
+    }
+
+    let content = read_editable_text(path, options.protect_binary)?;
+    let (content, bom) = split_bom(&content);
+    if content.is_empty() && edits.iter().any(|edit| !edit.old_str.is_empty()) {
+        return Err(empty_file_edit_error(path));
+    }
+
+    // Match against LF content without the BOM, as str_replace does
+    let line_ending = LineEnding::resolve(options.line_ending, Some(content));
+    let mut new_content = LineEnding::Lf.apply(content);
+
+    for (i, edit) in edits.iter().enumerate() {
+        let old_str = LineEnding::Lf.apply(&edit.old_str);
+        let new_str = LineEnding::Lf.apply(&edit.new_str);
+
+        let problem = match new_content.matches(old_str.as_str()).count() {
+            _ if old_str.is_empty() => Some("is empty"),
+            0 => Some("does not appear in the file"),
+            1 => None,
+            _ => Some("appears multiple times"),
+        };
+        if let Some(problem) = problem {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!(
+                    "Edit {} of {}: 'old_str' must appear exactly once in the file, but it {}. \
+                     No edits were applied.",
+                    i + 1,
+                    edits.len(),
+                    problem
+                ),
+                None,
+            ));
+        }
+
+        new_content = new_content.replacen(old_str.as_str(), &new_str, 1);
+    }
+
+    save_file_history(path, file_history)?;
+
+    let normalized_content = options.finish_file(&new_content, line_ending, bom);
+    std::fs::write(path, &normalized_content).map_err(|e| {
         ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
-            format!("Failed to read file: {}", e),
+            format!("Failed to write file: {}", e),
             None,
         )
     })?;
 
+    Ok(vec![
+        Content::text(format!(
+            "Successfully applied {} edits to {}. Review the file for errors, and use undo_edit to revert all of them if necessary.",
+            edits.len(),
+            path.display()
+        ))
+        .with_audience(vec![Role::Assistant]),
+        Content::text(format!(
+            "Applied {} edits to {}",
+            edits.len(),
+            path.display()
+        ))
+        .with_audience(vec![Role::User])
+        .with_priority(0.2),
+    ])
+}
+
+pub async fn text_editor_insert(
+    path: &PathBuf,
+    insert_line_spec: i64,
+    new_str: &str,
+    file_history: &std::sync::Arc<
+        std::sync::Mutex<std::collections::HashMap<PathBuf, Vec<String>>>,
+    >,
+    options: WriteOptions,
+) -> Result<Vec<Content>, ErrorData> {
+    // Check if file exists
+    if !path.exists() {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "File '{}' does not exist, you can write a new file with the `write` command",
+                path.display()
+            ),
+            None,
+        ));
+    }
+
+    // Read content
+    let content = read_editable_text(path, options.protect_binary)?;
+    let (content, bom) = split_bom(&content);
+
     // Save history for undo
     save_file_history(path, file_history)?;
 
+    let line_ending = LineEnding::resolve(options.line_ending, Some(content));
     let lines: Vec<&str> = content.lines().collect();
     let total_lines = lines.len();
 
@@ -467,14 +1260,15 @@ pub async fn text_editor_insert(
     }
 
     let new_content = new_lines.join("\n");
-    let normalized_content = normalize_line_endings(&new_content);
+    let normalized_content = line_ending.apply(&new_content);
 
     // Ensure the file ends with a newline
     let final_content = if !normalized_content.ends_with('\n') {
-        format!("{}\n", normalized_content)
+        format!("{}{}", normalized_content, line_ending.as_str())
     } else {
         normalized_content
     };
+    let final_content = options.finish_file(&final_content, line_ending, bom);
 
     std::fs::write(path, &final_content).map_err(|e| {
         ErrorData::new(
@@ -531,6 +1325,178 @@ pub async fn text_editor_insert(
     ])
 }
 
+pub async fn text_editor_append(
+    path: &PathBuf,
+    text: &str,
+    file_history: &std::sync::Arc<
+        std::sync::Mutex<std::collections::HashMap<PathBuf, Vec<String>>>,
+    >,
+    options: WriteOptions,
+) -> Result<Vec<Content>, ErrorData> {
+    // Read existing content, appending to a missing file creates it
+    let existing = if path.exists() {
+        read_editable_text(path, options.protect_binary)?
+    } else {
+        String::new()
+    };
+    let (existing, bom) = split_bom(&existing);
+
+    // Save history for undo
+    save_file_history(path, file_history)?;
+
+    let line_ending = LineEnding::resolve(options.line_ending, Some(existing));
+    let mut appended = line_ending.apply(text);
+    if !appended.ends_with('\n') {
+        appended.push_str(line_ending.as_str());
+    }
+
+    // Start the appended text on its own line if the file lacks a trailing newline
+    let mut new_content = existing.to_string();
+    if !new_content.is_empty() && !new_content.ends_with('\n') {
+        new_content.push_str(line_ending.as_str());
+    }
+    let start_line = new_content.lines().count() + 1;
+    new_content.push_str(&appended);
+    let new_content = options.finish_file(&new_content, line_ending, bom);
+
+    std::fs::write(path, &new_content).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to write file: {}", e),
+            None,
+        )
+    })?;
+
+    // Try to detect the language from the file extension
+    let language = lang::get_language_identifier(path);
+
+    let snippet = appended
+        .lines()
+        .enumerate()
+        .map(|(i, line)| format!("{}: {}", start_line + i, line))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let output = formatdoc! {r#"
+        ```{language}
+        {snippet}
+        ```
+        "#,
+        language=language,
+        snippet=snippet
+    };
+
+    Ok(vec![
+        Content::text(format!(
+            "Appended {} lines to {} starting at line {}",
+            appended.lines().count(),
+            path.display(),
+            start_line
+        ))
+        .with_audience(vec![Role::Assistant]),
+        Content::text(output)
+            .with_audience(vec![Role::User])
+            .with_priority(0.2),
+    ])
+}
+
+pub async fn text_editor_copy(
+    source: &PathBuf,
+    destination: &PathBuf,
+    overwrite: bool,
+    file_history: &std::sync::Arc<
+        std::sync::Mutex<std::collections::HashMap<PathBuf, Vec<String>>>,
+    >,
+) -> Result<Vec<Content>, ErrorData> {
+    if !source.is_file() {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("'{}' is not a file that can be copied", source.display()),
+            None,
+        ));
+    }
+    if destination.is_dir() {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "'{}' is a directory, give the full path of the new file",
+                destination.display()
+            ),
+            None,
+        ));
+    }
+
+    let overwriting = destination.exists();
+    if overwriting {
+        if !overwrite {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!(
+                    "'{}' already exists. Set `overwrite` to true to replace it",
+                    destination.display()
+                ),
+                None,
+            ));
+        }
+        // Replacing the destination is an edit to it, so it can be undone
+        save_file_history(destination, file_history)?;
+    }
+
+    // fs::copy streams the contents, so large files are never held in memory
+    let bytes = std::fs::copy(source, destination).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to copy file: {}", e),
+            None,
+        )
+    })?;
+
+    Ok(vec![Content::text(format!(
+        "Copied {} to {} ({} bytes){}",
+        source.display(),
+        destination.display(),
+        bytes,
+        if overwriting {
+            ", replacing the existing file"
+        } else {
+            ""
+        }
+    ))])
+}
+
+pub async fn text_editor_create_dir(path: &Path) -> Result<Vec<Content>, ErrorData> {
+    if path.is_dir() {
+        return Ok(vec![Content::text(format!(
+            "Directory {} already exists",
+            path.display()
+        ))]);
+    }
+    if path.exists() {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "Cannot create directory '{}': a file with that name already exists",
+                path.display()
+            ),
+            None,
+        ));
+    }
+
+    // Missing parents are created too, like `mkdir -p`
+    std::fs::create_dir_all(path).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to create directory: {}", e),
+            None,
+        )
+    })?;
+
+    Ok(vec![Content::text(format!(
+        "Created directory {}",
+        path.display()
+    ))])
+}
+
 pub async fn text_editor_undo(
     path: &PathBuf,
     file_history: &std::sync::Arc<
@@ -583,6 +1549,19 @@ pub fn save_file_history(
     } else {
         String::new()
     };
-    history.entry(path.clone()).or_default().push(content);
+    let versions = history.entry(path.clone()).or_default();
+    versions.push(content);
+    let excess = versions.len().saturating_sub(max_undo_versions());
+    versions.drain(..excess);
     Ok(())
 }
+
+/// Number of undo versions kept per file, set with GOOSE_EDITOR_MAX_UNDO_VERSIONS.
+/// The oldest versions are dropped first; at least one is always kept.
+fn max_undo_versions() -> usize {
+    std::env::var("GOOSE_EDITOR_MAX_UNDO_VERSIONS")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_UNDO_VERSIONS)
+        .max(1)
+}
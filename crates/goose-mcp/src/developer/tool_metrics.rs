@@ -0,0 +1,113 @@
+use rmcp::model::{CallToolResult, Content, ErrorData, RawContent, ResourceContents, Role};
+use serde_json::{json, Value};
+use std::time::Instant;
+
+/// Key of the metrics in a tool result's structured content
+pub const TOOL_METRICS_KEY: &str = "tool_metrics";
+
+/// Timing and size telemetry for a single developer tool call.
+///
+/// The metrics are added to the structured content of successful results, which is never
+/// part of the conversation, so they cost the model nothing while the UI and logs can
+/// aggregate them without parsing free text.
+pub struct ToolMetrics {
+    tool: &'static str,
+    started: Instant,
+    exit_code: Option<i32>,
+}
+
+impl ToolMetrics {
+    pub fn start(tool: &'static str) -> Self {
+        Self {
+            tool,
+            started: Instant::now(),
+            exit_code: None,
+        }
+    }
+
+    pub fn set_exit_code(&mut self, exit_code: Option<i32>) {
+        self.exit_code = exit_code;
+    }
+
+    /// Add the metrics to a successful result's structured content, next to anything the
+    /// tool put there; errors are passed through unchanged
+    pub fn attach(
+        self,
+        result: Result<CallToolResult, ErrorData>,
+    ) -> Result<CallToolResult, ErrorData> {
+        result.map(|mut result| {
+            let mut metrics = json!({
+                "tool": self.tool,
+                "duration_ms": self.started.elapsed().as_millis() as u64,
+                "output_bytes": output_bytes(&result.content),
+            });
+            if let Some(exit_code) = self.exit_code {
+                metrics["exit_code"] = json!(exit_code);
+            }
+            // Structured results that aren't objects are left as the tool made them
+            if let Value::Object(structured) =
+                result.structured_content.get_or_insert_with(|| json!({}))
+            {
+                structured.insert(TOOL_METRICS_KEY.to_string(), metrics);
+            }
+            result
+        })
+    }
+}
+
+/// Size in bytes of the content the model receives, i.e. items without an audience or
+/// addressed to the assistant
+pub fn output_bytes(content: &[Content]) -> usize {
+    content
+        .iter()
+        .filter(|item| {
+            item.audience()
+                .is_none_or(|audience| audience.contains(&Role::Assistant))
+        })
+        .map(|item| match &item.raw {
+            RawContent::Text(text) => text.text.len(),
+            RawContent::Image(image) => image.data.len(),
+            RawContent::Audio(audio) => audio.raw.data.len(),
+            RawContent::Resource(resource) => match &resource.resource {
+                ResourceContents::TextResourceContents { text, .. } => text.len(),
+                ResourceContents::BlobResourceContents { blob, .. } => blob.len(),
+            },
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attach_metrics() {
+        let mut metrics = ToolMetrics::start("shell");
+        metrics.set_exit_code(Some(2));
+        let result = metrics
+            .attach(Ok(CallToolResult::success(vec![
+                Content::text("hello").with_audience(vec![Role::Assistant]),
+                Content::text("hello, user").with_audience(vec![Role::User]),
+            ])))
+            .unwrap();
+
+        // Nothing is added to the content the model or the user sees
+        assert_eq!(result.content.len(), 2);
+        let metrics = &result.structured_content.unwrap()[TOOL_METRICS_KEY];
+        assert_eq!(metrics["tool"], "shell");
+        assert_eq!(metrics["output_bytes"], 5);
+        assert_eq!(metrics["exit_code"], 2);
+        assert!(metrics["duration_ms"].is_u64());
+
+        // Structured content from the tool is kept alongside the metrics
+        let mut result = CallToolResult::success(vec![Content::text("done")]);
+        result.structured_content = Some(json!({ "truncation": { "lines": 10 } }));
+        let structured = ToolMetrics::start("shell")
+            .attach(Ok(result))
+            .unwrap()
+            .structured_content
+            .unwrap();
+        assert_eq!(structured["truncation"]["lines"], 10);
+        assert_eq!(structured[TOOL_METRICS_KEY]["tool"], "shell");
+    }
+}
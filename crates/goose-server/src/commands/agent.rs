@@ -1,8 +1,10 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use crate::configuration;
 use crate::state;
 use anyhow::Result;
+use axum::middleware;
 use etcetera::{choose_app_strategy, AppStrategy};
 use goose::agents::Agent;
 use goose::config::APP_STRATEGY;
@@ -44,16 +46,31 @@ pub async fn run() -> Result<()> {
 
     // NEW: Provide scheduler access to the agent
     agent_ref.set_scheduler(scheduler_instance).await;
+    agent_ref
+        .set_session_store(app_state.session_store.clone())
+        .await;
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    let app = crate::routes::configure(app_state).layer(cors);
+    let mut app = crate::routes::configure(app_state.clone()).layer(cors);
+
+    if crate::routes::utils::allow_local_noauth() {
+        info!("GOOSE_SERVER_ALLOW_LOCAL_NOAUTH is set, loopback requests without an Origin skip secret key verification");
+        app = app.layer(middleware::from_fn_with_state(
+            app_state,
+            crate::routes::utils::authenticate_loopback_callers,
+        ));
+    }
 
     let listener = tokio::net::TcpListener::bind(settings.socket_addr()).await?;
     info!("listening on {}", listener.local_addr()?);
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
@@ -14,8 +14,9 @@ use rmcp::model::{
 use utoipa::{OpenApi, ToSchema};
 
 use goose::conversation::message::{
-    ContextLengthExceeded, FrontendToolRequest, Message, MessageContent, RedactedThinkingContent,
-    SummarizationRequested, ThinkingContent, ToolConfirmationRequest, ToolRequest, ToolResponse,
+    ContentKind, ContextLengthExceeded, FrontendToolRequest, Message, MessageContent,
+    RedactedThinkingContent, SummarizationRequested, ThinkingContent, ToolConfirmationRequest,
+    ToolRequest, ToolResponse,
 };
 use utoipa::openapi::schema::{
     AdditionalProperties, AnyOfBuilder, ArrayBuilder, ObjectBuilder, OneOfBuilder, Schema,
@@ -373,15 +374,21 @@ impl<'__s> ToSchema<'__s> for AnnotatedSchema {
         super::routes::agent::start_agent,
         super::routes::agent::resume_agent,
         super::routes::agent::get_tools,
+        super::routes::agent::get_active_tools,
         super::routes::agent::add_sub_recipes,
         super::routes::agent::extend_prompt,
         super::routes::agent::update_agent_provider,
         super::routes::agent::update_router_tool_selector,
         super::routes::agent::update_session_config,
+        super::routes::agent::preview_tools,
         super::routes::reply::confirm_permission,
         super::routes::context::manage_context,
         super::routes::session::list_sessions,
         super::routes::session::get_session_history,
+        super::routes::session::get_latest_session,
+        super::routes::session::import_session,
+        super::routes::session::describe_session,
+        super::routes::session::get_token_usage,
         super::routes::schedule::create_schedule,
         super::routes::schedule::list_schedules,
         super::routes::schedule::delete_schedule,
@@ -413,8 +420,15 @@ impl<'__s> ToSchema<'__s> for AnnotatedSchema {
         super::routes::reply::PermissionConfirmationRequest,
         super::routes::context::ContextManageRequest,
         super::routes::context::ContextManageResponse,
+        super::routes::agent::ActiveToolInfo,
+        super::routes::agent::ActiveToolsResponse,
         super::routes::session::SessionListResponse,
+        super::routes::session::SessionErrorResponse,
+        super::routes::session::SessionErrorCode,
         super::routes::session::SessionHistoryResponse,
+        super::routes::session::DescribeSessionResponse,
+        super::routes::session::TokenUsageResponse,
+        super::routes::session::DailyTokenUsage,
         Message,
         MessageContent,
         ContentSchema,
@@ -431,6 +445,7 @@ impl<'__s> ToSchema<'__s> for AnnotatedSchema {
         ToolConfirmationRequest,
         ThinkingContent,
         RedactedThinkingContent,
+        ContentKind,
         FrontendToolRequest,
         ResourceContentsSchema,
         ContextLengthExceeded,
@@ -493,6 +508,8 @@ impl<'__s> ToSchema<'__s> for AnnotatedSchema {
         super::routes::agent::ResumeAgentRequest,
         super::routes::agent::StartAgentResponse,
         super::routes::agent::ErrorResponse,
+        super::routes::agent::PreviewToolsRequest,
+        super::routes::agent::PreviewToolsResponse,
     ))
 )]
 pub struct ApiDoc;
@@ -8,7 +8,7 @@ use axum::{
     Json, Router,
 };
 use goose::config::PermissionManager;
-use goose::conversation::message::Message;
+use goose::conversation::message::{Message, ToolRequest};
 use goose::conversation::Conversation;
 use goose::model::ModelConfig;
 use goose::providers::create;
@@ -20,6 +20,7 @@ use goose::{
     config::permission::PermissionLevel,
 };
 use goose::{config::Config, recipe::SubRecipe};
+use rmcp::model::Tool;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
@@ -72,6 +73,27 @@ pub struct GetToolsQuery {
     session_id: String,
 }
 
+/// A tool the agent can currently call
+#[derive(Serialize, utoipa::ToSchema, Debug, PartialEq)]
+pub struct ActiveToolInfo {
+    /// Prefixed tool name, as seen by the model
+    name: String,
+    /// Extension that provides the tool, if it could be determined
+    extension: Option<String>,
+    /// Whether the tool is annotated as not modifying its environment
+    read_only: bool,
+    /// Whether the tool may perform destructive updates
+    destructive: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ActiveToolsResponse {
+    /// Names of the extensions currently enabled for the agent
+    extensions: Vec<String>,
+    /// Tools currently available to the agent, sorted by name
+    tools: Vec<ActiveToolInfo>,
+}
+
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct UpdateRouterToolSelectorRequest {
     #[allow(dead_code)]
@@ -97,6 +119,23 @@ pub struct StartAgentResponse {
     messages: Vec<Message>,
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct PreviewToolsRequest {
+    /// Conversation to send to the provider
+    messages: Vec<Message>,
+}
+
+/// Tool calls the model asked for, none of which were executed
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PreviewToolsResponse {
+    /// Requests for tools that run in the frontend
+    frontend_requests: Vec<ToolRequest>,
+    /// Requests for every other tool, in the order the model issued them
+    other_requests: Vec<ToolRequest>,
+    /// The model's response with frontend tool requests removed
+    message: Message,
+}
+
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct ErrorResponse {
     error: String,
@@ -138,6 +177,7 @@ async fn start_agent(
         accumulated_output_tokens: Some(0),
         extension_data: Default::default(),
         recipe: payload.recipe,
+        last_activity: None,
     };
 
     let session_path = match session::get_path(session::Identifier::Name(session_id.clone())) {
@@ -300,6 +340,82 @@ async fn get_tools(
     Ok(Json(tools))
 }
 
+/// Describe a tool, attributing it to the enabled extension whose prefix it carries.
+fn active_tool_info(tool: &Tool, extension_names: &[String]) -> ActiveToolInfo {
+    let extension = extension_names
+        .iter()
+        .filter(|name| {
+            tool.name
+                .strip_prefix(name.as_str())
+                .is_some_and(|rest| rest.starts_with("__"))
+        })
+        .max_by_key(|name| name.len())
+        .cloned()
+        .or_else(|| {
+            tool.name
+                .split_once("__")
+                .map(|(prefix, _)| prefix.to_string())
+        });
+
+    let read_only = tool
+        .annotations
+        .as_ref()
+        .and_then(|a| a.read_only_hint)
+        .unwrap_or(false);
+    // Per the MCP spec, tools that are not read-only are assumed destructive unless told otherwise
+    let destructive = !read_only
+        && tool
+            .annotations
+            .as_ref()
+            .and_then(|a| a.destructive_hint)
+            .unwrap_or(true);
+
+    ActiveToolInfo {
+        name: tool.name.to_string(),
+        extension,
+        read_only,
+        destructive,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/tools",
+    responses(
+        (status = 200, description = "Active extensions and tools retrieved successfully", body = ActiveToolsResponse),
+        (status = 401, description = "Unauthorized - invalid secret key"),
+        (status = 424, description = "Agent not initialized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn get_active_tools(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<ActiveToolsResponse>, StatusCode> {
+    verify_secret_key(&headers, &state)?;
+
+    let agent = state.get_agent().await;
+
+    let mut extensions: Vec<String> = agent
+        .extension_manager
+        .get_extensions_info()
+        .await
+        .into_iter()
+        .map(|info| info.name)
+        .collect();
+    extensions.sort();
+
+    let mut tools: Vec<ActiveToolInfo> = agent
+        .list_tools(None)
+        .await
+        .iter()
+        .map(|tool| active_tool_info(tool, &extensions))
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Json(ActiveToolsResponse { extensions, tools }))
+}
+
 #[utoipa::path(
     post,
     path = "/agent/update_provider",
@@ -424,12 +540,53 @@ async fn update_session_config(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/agent/preview_tools",
+    request_body = PreviewToolsRequest,
+    responses(
+        (status = 200, description = "Planned tool calls retrieved successfully", body = PreviewToolsResponse),
+        (status = 401, description = "Unauthorized - invalid secret key"),
+        (status = 424, description = "Agent not initialized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn preview_tools(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<PreviewToolsRequest>,
+) -> Result<Json<PreviewToolsResponse>, Json<ErrorResponse>> {
+    verify_secret_key(&headers, &state).map_err(|_| {
+        Json(ErrorResponse {
+            error: "Unauthorized - Invalid or missing API key".to_string(),
+        })
+    })?;
+
+    let agent = state.get_agent().await;
+    let preview = agent
+        .preview_tool_calls(&payload.messages)
+        .await
+        .map_err(|e| {
+            error!("Failed to preview tool calls: {}", e);
+            Json(ErrorResponse {
+                error: format!("Failed to preview tool calls: {}", e),
+            })
+        })?;
+
+    Ok(Json(PreviewToolsResponse {
+        frontend_requests: preview.frontend_requests,
+        other_requests: preview.other_requests,
+        message: preview.filtered_message,
+    }))
+}
+
 pub fn routes(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/agent/start", post(start_agent))
         .route("/agent/resume", post(resume_agent))
         .route("/agent/prompt", post(extend_prompt))
         .route("/agent/tools", get(get_tools))
+        .route("/tools", get(get_active_tools))
         .route("/agent/update_provider", post(update_agent_provider))
         .route(
             "/agent/update_router_tool_selector",
@@ -437,5 +594,134 @@ pub fn routes(state: Arc<AppState>) -> Router {
         )
         .route("/agent/session_config", post(update_session_config))
         .route("/agent/add_sub_recipes", post(add_sub_recipes))
+        .route("/agent/preview_tools", post(preview_tools))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use goose::agents::Agent;
+    use goose::providers::base::{Provider, ProviderMetadata, ProviderUsage, Usage};
+    use goose::providers::errors::ProviderError;
+    use mcp_core::tool::ToolCall;
+    use rmcp::model::ToolAnnotations;
+    use rmcp::object;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    /// Provider that always asks to run the same shell command
+    #[derive(Clone)]
+    struct ToolCallingProvider {
+        model_config: ModelConfig,
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for ToolCallingProvider {
+        fn metadata() -> ProviderMetadata {
+            ProviderMetadata::empty()
+        }
+
+        async fn complete_with_model(
+            &self,
+            _model_config: &ModelConfig,
+            _system: &str,
+            _messages: &[Message],
+            _tools: &[Tool],
+        ) -> Result<(Message, ProviderUsage), ProviderError> {
+            Ok((
+                Message::assistant()
+                    .with_text("Listing files")
+                    .with_tool_request(
+                        "call_1",
+                        Ok(ToolCall::new("developer__shell", json!({"command": "ls"}))),
+                    ),
+                ProviderUsage::new("mock".to_string(), Usage::default()),
+            ))
+        }
+
+        fn get_model_config(&self) -> ModelConfig {
+            self.model_config.clone()
+        }
+    }
+
+    fn tool(name: &str, read_only: Option<bool>, destructive: Option<bool>) -> Tool {
+        Tool::new(name.to_string(), "test tool", object!({})).annotate(ToolAnnotations {
+            title: None,
+            read_only_hint: read_only,
+            destructive_hint: destructive,
+            idempotent_hint: None,
+            open_world_hint: None,
+        })
+    }
+
+    #[test]
+    fn test_active_tool_info_attributes_extension_and_annotations() {
+        let extensions = vec!["developer".to_string(), "developer__extra".to_string()];
+
+        assert_eq!(
+            active_tool_info(
+                &tool("developer__extra__view", Some(true), None),
+                &extensions
+            ),
+            ActiveToolInfo {
+                name: "developer__extra__view".to_string(),
+                extension: Some("developer__extra".to_string()),
+                read_only: true,
+                destructive: false,
+            }
+        );
+
+        let info = active_tool_info(&tool("developer__shell", None, None), &extensions);
+        assert_eq!(info.extension.as_deref(), Some("developer"));
+        assert!(!info.read_only);
+        assert!(info.destructive);
+
+        let info = active_tool_info(
+            &tool("platform__manage_schedule", Some(false), Some(false)),
+            &extensions,
+        );
+        assert_eq!(info.extension.as_deref(), Some("platform"));
+        assert!(!info.destructive);
+
+        let info = active_tool_info(&tool("final_output", None, None), &extensions);
+        assert_eq!(info.extension, None);
+    }
+
+    #[tokio::test]
+    async fn test_preview_tools_returns_planned_calls() {
+        let agent = Agent::new();
+        agent
+            .update_provider(Arc::new(ToolCallingProvider {
+                model_config: ModelConfig::new("test-model").unwrap(),
+            }))
+            .await
+            .unwrap();
+        let state = AppState::new(Arc::new(agent), "test-secret".to_string());
+
+        let request = Request::builder()
+            .uri("/agent/preview_tools")
+            .method("POST")
+            .header("content-type", "application/json")
+            .header("x-secret-key", "test-secret")
+            .body(Body::from(
+                json!({"messages": [Message::user().with_text("list the files")]}).to_string(),
+            ))
+            .unwrap();
+        let response = routes(state).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let preview: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(preview["frontend_requests"], json!([]));
+        let other_requests = preview["other_requests"].as_array().unwrap();
+        assert_eq!(other_requests.len(), 1);
+        assert_eq!(other_requests[0]["id"], "call_1");
+        assert_eq!(
+            other_requests[0]["toolCall"]["value"]["name"],
+            "developer__shell"
+        );
+    }
+}
@@ -5,6 +5,7 @@ pub mod config_management;
 pub mod context;
 pub mod extension;
 pub mod health;
+pub mod rate_limit;
 pub mod recipe;
 pub mod recipe_utils;
 pub mod reply;
@@ -0,0 +1,196 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header::RETRY_AFTER, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use goose::config::Config;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Requests per minute allowed on the session routes for each caller
+pub const DEFAULT_SESSION_RATE_LIMIT: u32 = 120;
+/// Requests per minute allowed on the session insights route, which reads every session
+pub const DEFAULT_INSIGHTS_RATE_LIMIT: u32 = 10;
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+// Most callers tracked at once. Callers whose window has ended are dropped first, then the
+// caller whose window started earliest, so the map never grows past this
+const MAX_TRACKED_CALLERS: usize = 1024;
+
+/// Fixed window rate limiter keyed by caller
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    /// A limiter allowing `limit` requests per `window`; a limit of 0 disables it
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// A per-minute limiter configured by `key`, falling back to `default`
+    pub fn per_minute_from_config(key: &str, default: u32) -> Arc<Self> {
+        let limit = Config::global().get_param(key).unwrap_or(default);
+        Arc::new(Self::new(limit, RATE_LIMIT_WINDOW))
+    }
+
+    /// Record a request from `caller`, returning how long to wait when it is over the limit
+    pub fn check(&self, caller: &str) -> Result<(), Duration> {
+        self.check_at(caller, Instant::now())
+    }
+
+    fn check_at(&self, caller: &str, now: Instant) -> Result<(), Duration> {
+        if self.limit == 0 {
+            return Ok(());
+        }
+
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if windows.len() >= MAX_TRACKED_CALLERS && !windows.contains_key(caller) {
+            windows.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+            if windows.len() >= MAX_TRACKED_CALLERS {
+                let oldest = windows
+                    .iter()
+                    .min_by_key(|(_, (started, _))| *started)
+                    .map(|(caller, _)| caller.clone());
+                if let Some(oldest) = oldest {
+                    windows.remove(&oldest);
+                }
+            }
+        }
+
+        let (started, count) = windows.entry(caller.to_string()).or_insert((now, 0));
+        if now.duration_since(*started) >= self.window {
+            *started = now;
+            *count = 0;
+        }
+
+        if *count >= self.limit {
+            return Err(self.window - now.duration_since(*started));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+// Callers are identified by the connection's remote address. Headers are not verified
+// before the limiter runs, so keying on them would let a caller pick a fresh budget
+fn caller_key(request: &Request) -> String {
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => addr.ip().to_string(),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Middleware rejecting callers over the limiter's budget with 429 and a `Retry-After` header
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter.check(&caller_key(&request)) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            // Round up so clients never retry before the window has ended
+            let retry_after_secs =
+                retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(RETRY_AFTER, retry_after_secs.max(1).to_string())],
+                "Too many requests",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_limits_each_caller_per_window() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start).is_ok());
+        assert!(limiter.check_at("a", start).is_ok());
+        assert_eq!(
+            limiter.check_at("a", start + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+        // Other callers have their own budget
+        assert!(limiter.check_at("b", start).is_ok());
+        // A new window starts once the old one has ended
+        assert!(limiter
+            .check_at("a", start + Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_tracked_callers_are_capped() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        let start = Instant::now();
+
+        for i in 0..MAX_TRACKED_CALLERS + 10 {
+            let now = start + Duration::from_millis(i as u64);
+            assert!(limiter.check_at(&format!("caller-{}", i), now).is_ok());
+        }
+        let windows = limiter.windows.lock().unwrap();
+        assert_eq!(windows.len(), MAX_TRACKED_CALLERS);
+        // The earliest callers were evicted to make room
+        assert!(!windows.contains_key("caller-0"));
+        assert!(windows.contains_key(&format!("caller-{}", MAX_TRACKED_CALLERS + 9)));
+    }
+
+    #[test]
+    fn test_zero_limit_disables_limiting() {
+        let limiter = RateLimiter::new(0, Duration::from_secs(60));
+        for _ in 0..100 {
+            assert!(limiter.check("a").is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_returns_retry_after() {
+        let limiter = Arc::new(RateLimiter::new(1, Duration::from_secs(60)));
+        let app = Router::new()
+            .route("/limited", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(limiter, rate_limit));
+
+        let request = |key: &str| {
+            let mut request = http::Request::builder()
+                .uri("/limited")
+                .header("X-Secret-Key", key)
+                .body(Body::empty())
+                .unwrap();
+            let addr: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+            request.extensions_mut().insert(ConnectInfo(addr));
+            request
+        };
+
+        let response = app.clone().oneshot(request("first")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // A different key from the same address shares its budget
+        let response = app.oneshot(request("second")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after));
+    }
+}
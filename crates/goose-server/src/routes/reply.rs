@@ -9,7 +9,7 @@ use axum::{
 };
 use bytes::Bytes;
 use futures::{stream::StreamExt, Stream};
-use goose::conversation::message::{Message, MessageContent};
+use goose::conversation::message::{ContentKind, Message, MessageContent};
 use goose::conversation::Conversation;
 use goose::{
     agents::{AgentEvent, SessionConfig},
@@ -90,6 +90,7 @@ struct ChatRequest {
     session_id: Option<String>,
     recipe_name: Option<String>,
     recipe_version: Option<String>,
+    max_history_messages: Option<usize>,
 }
 
 pub struct SseResponse {
@@ -131,6 +132,9 @@ impl IntoResponse for SseResponse {
 enum MessageEvent {
     Message {
         message: Message,
+        /// Whether the message carries reasoning, answer text or tool calls
+        #[serde(skip_serializing_if = "Option::is_none")]
+        kind: Option<ContentKind>,
     },
     Error {
         error: String,
@@ -205,6 +209,7 @@ async fn reply_handler(
         tracing::error!("session_id is required but was not provided");
         StatusCode::BAD_REQUEST
     })?;
+    let max_history_messages = request.max_history_messages;
 
     let task_cancel = cancel_token.clone();
     let task_tx = tx.clone();
@@ -252,6 +257,7 @@ async fn reply_handler(
             execution_mode: None,
             max_turns: None,
             retry_config: None,
+            max_history_messages,
         };
 
         let mut stream = match agent
@@ -313,7 +319,8 @@ async fn reply_handler(
                             }
 
                             all_messages.push(message.clone());
-                            stream_event(MessageEvent::Message { message }, &tx, &cancel_token).await;
+                            let kind = message.content_kind();
+                            stream_event(MessageEvent::Message { message, kind }, &tx, &cancel_token).await;
                         }
                         Ok(Some(Ok(AgentEvent::HistoryReplaced(new_messages)))) => {
                             // Replace the message history with the compacted messages
@@ -614,6 +621,7 @@ mod tests {
                         session_id: Some("test-session".to_string()),
                         recipe_name: None,
                         recipe_version: None,
+                        max_history_messages: None,
                     })
                     .unwrap(),
                 ))
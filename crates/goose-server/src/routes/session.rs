@@ -1,23 +1,114 @@
+use super::rate_limit::{
+    rate_limit, RateLimiter, DEFAULT_INSIGHTS_RATE_LIMIT, DEFAULT_SESSION_RATE_LIMIT,
+};
 use super::utils::verify_secret_key;
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::state::AppState;
 use axum::{
-    extract::{Path, State},
-    http::{HeaderMap, StatusCode},
-    routing::{delete, get, put},
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
     Json, Router,
 };
+use goose::config::Config;
 use goose::conversation::message::Message;
 use goose::session;
-use goose::session::info::{get_valid_sorted_sessions, SessionInfo, SortOrder};
-use goose::session::SessionMetadata;
+use goose::session::info::{ModifiedRange, SessionInfo, SortOrder};
+use goose::session::{SessionMetadata, SessionStore};
 use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 use utoipa::ToSchema;
 
+/// Machine-readable reason a session route failed
+#[derive(Serialize, ToSchema, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionErrorCode {
+    Unauthorized,
+    InvalidSessionId,
+    InvalidRequest,
+    SessionNotFound,
+    SessionUnreadable,
+    Internal,
+}
+
+impl SessionErrorCode {
+    fn status(self) -> StatusCode {
+        match self {
+            SessionErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            SessionErrorCode::InvalidSessionId | SessionErrorCode::InvalidRequest => {
+                StatusCode::BAD_REQUEST
+            }
+            SessionErrorCode::SessionNotFound => StatusCode::NOT_FOUND,
+            SessionErrorCode::SessionUnreadable | SessionErrorCode::Internal => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+/// JSON error body returned by the session routes alongside the HTTP status
+#[derive(Serialize, ToSchema, Debug)]
+pub struct SessionErrorResponse {
+    code: SessionErrorCode,
+    message: String,
+}
+
+impl SessionErrorResponse {
+    fn new(code: SessionErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_session_id(session_id: &str) -> Self {
+        Self::new(
+            SessionErrorCode::InvalidSessionId,
+            format!("Invalid session id '{}'", session_id),
+        )
+    }
+
+    fn not_found(session_id: &str) -> Self {
+        Self::new(
+            SessionErrorCode::SessionNotFound,
+            format!("Session '{}' not found", session_id),
+        )
+    }
+}
+
+// Lets `verify_secret_key(..)?` keep working in handlers returning SessionErrorResponse
+impl From<StatusCode> for SessionErrorResponse {
+    fn from(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => {
+                Self::new(SessionErrorCode::Unauthorized, "Invalid or missing API key")
+            }
+            StatusCode::BAD_REQUEST => {
+                Self::new(SessionErrorCode::InvalidRequest, "Invalid request")
+            }
+            StatusCode::NOT_FOUND => {
+                Self::new(SessionErrorCode::SessionNotFound, "Session not found")
+            }
+            _ => Self::new(SessionErrorCode::Internal, "Internal server error"),
+        }
+    }
+}
+
+impl IntoResponse for SessionErrorResponse {
+    fn into_response(self) -> Response {
+        (self.code.status(), Json(self)).into_response()
+    }
+}
+
 #[derive(Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionListResponse {
@@ -25,6 +116,54 @@ pub struct SessionListResponse {
     sessions: Vec<SessionInfo>,
 }
 
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSessionsQuery {
+    /// Only include sessions modified at or after this time (RFC 3339 timestamp or YYYY-MM-DD)
+    since: Option<String>,
+    /// Only include sessions modified at or before this time (RFC 3339 timestamp or YYYY-MM-DD)
+    until: Option<String>,
+}
+
+/// Parse a `since`/`until` bound. A bare date covers the whole day, so it maps to the
+/// start of the day for `since` and the end of the day for `until`.
+fn parse_time_bound(value: &str, end_of_day: bool) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let time = if end_of_day {
+        NaiveTime::from_hms_opt(23, 59, 59)?
+    } else {
+        NaiveTime::MIN
+    };
+    Some(date.and_time(time).and_utc())
+}
+
+impl ListSessionsQuery {
+    fn modified_range(&self) -> Result<ModifiedRange, SessionErrorResponse> {
+        let parse = |value: &Option<String>, end_of_day| {
+            value
+                .as_deref()
+                .map(|v| {
+                    parse_time_bound(v, end_of_day).ok_or_else(|| {
+                        SessionErrorResponse::new(
+                            SessionErrorCode::InvalidRequest,
+                            format!("Invalid timestamp '{}', expected RFC 3339 or YYYY-MM-DD", v),
+                        )
+                    })
+                })
+                .transpose()
+        };
+
+        Ok(ModifiedRange {
+            since: parse(&self.since, false)?,
+            until: parse(&self.until, true)?,
+        })
+    }
+}
+
 #[derive(Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionHistoryResponse {
@@ -32,8 +171,41 @@ pub struct SessionHistoryResponse {
     session_id: String,
     /// Session metadata containing creation time and other details
     metadata: SessionMetadata,
-    /// List of messages in the session conversation
+    /// List of messages in the session conversation, limited to the requested range
     messages: Vec<Message>,
+    /// Total number of messages in the session, regardless of the requested range
+    total_messages: usize,
+}
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionHistoryQuery {
+    /// Index of the first message to return (inclusive, defaults to 0)
+    from: Option<usize>,
+    /// Index after the last message to return (exclusive, defaults to the total count)
+    to: Option<usize>,
+}
+
+impl SessionHistoryQuery {
+    /// Resolve the requested message range against the total count. Bounds past the end
+    /// are clamped, but a range whose start comes after its end is rejected.
+    fn message_range(&self, total: usize) -> Result<std::ops::Range<usize>, SessionErrorResponse> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err(SessionErrorResponse::new(
+                    SessionErrorCode::InvalidRequest,
+                    format!(
+                        "Invalid message range: from ({}) is after to ({})",
+                        from, to
+                    ),
+                ));
+            }
+        }
+
+        let to = self.to.unwrap_or(total).min(total);
+        let from = self.from.unwrap_or(0).min(to);
+        Ok(from..to)
+    }
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -58,6 +230,105 @@ pub struct SessionInsights {
     total_tokens: i64,
     /// Activity trend for the last 7 days
     recent_activity: Vec<(String, usize)>,
+    /// Sessions whose last recorded activity is older than the idle threshold
+    idle_sessions: usize,
+}
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInsightsQuery {
+    /// Minutes without activity after which a session counts as idle (defaults to 60)
+    idle_minutes: Option<u32>,
+}
+
+const DEFAULT_IDLE_SESSION_MINUTES: u32 = 60;
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenUsageQuery {
+    /// Number of days to report, ending today (defaults to 30, at most 365)
+    days: Option<u32>,
+}
+
+const DEFAULT_TOKEN_USAGE_DAYS: u32 = 30;
+const MAX_TOKEN_USAGE_DAYS: u32 = 365;
+
+#[derive(Serialize, ToSchema, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyTokenUsage {
+    /// Day in YYYY-MM-DD format (UTC)
+    date: String,
+    /// Tokens used by sessions last modified on this day
+    total_tokens: i64,
+    /// Number of sessions last modified on this day
+    sessions: usize,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenUsageResponse {
+    /// One entry per day, oldest first, including days without usage
+    days: Vec<DailyTokenUsage>,
+    /// Tokens used across the whole period
+    total_tokens: i64,
+}
+
+/// Whether insights describes unnamed sessions before counting them, set with
+/// GOOSE_AUTO_DESCRIBE_SESSIONS
+fn auto_describe_sessions() -> bool {
+    Config::global()
+        .get_param("GOOSE_AUTO_DESCRIBE_SESSIONS")
+        .unwrap_or(false)
+}
+
+/// Directories insights rolls sessions up to, so sessions started in different
+/// subdirectories of one project count towards that project. Set with
+/// GOOSE_INSIGHTS_PROJECT_ROOTS, a list of paths where a leading `~` is the home directory.
+fn insights_project_roots() -> Vec<PathBuf> {
+    Config::global()
+        .get_param::<Vec<String>>("GOOSE_INSIGHTS_PROJECT_ROOTS")
+        .unwrap_or_default()
+        .into_iter()
+        .map(|root| expand_home(&root))
+        .collect()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest.trim_start_matches('/'),
+        _ => return PathBuf::from(path),
+    };
+    match etcetera::home_dir() {
+        Ok(home) if rest.is_empty() => home,
+        Ok(home) => home.join(rest),
+        Err(_) => PathBuf::from(path),
+    }
+}
+
+/// The directory a session is counted under: the most specific root that contains its
+/// working directory, or the working directory itself
+fn project_dir(working_dir: &std::path::Path, roots: &[PathBuf]) -> String {
+    roots
+        .iter()
+        .filter(|root| working_dir.starts_with(root))
+        .max_by_key(|root| root.components().count())
+        .map_or(working_dir, |root| root.as_path())
+        .to_string_lossy()
+        .to_string()
+}
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DescribeSessionQuery {
+    /// Ask the provider to name the session instead of using its first message (defaults to false)
+    use_provider: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DescribeSessionResponse {
+    /// The new description, or null when the session already had one or has no user messages
+    description: Option<String>,
 }
 
 #[derive(Serialize, ToSchema, Debug)]
@@ -71,25 +342,31 @@ pub struct ActivityHeatmapCell {
 #[utoipa::path(
     get,
     path = "/sessions",
+    params(ListSessionsQuery),
     responses(
         (status = 200, description = "List of available sessions retrieved successfully", body = SessionListResponse),
-        (status = 401, description = "Unauthorized - Invalid or missing API key"),
-        (status = 500, description = "Internal server error")
+        (status = 400, description = "Bad request - Invalid since or until timestamp", body = SessionErrorResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
     ),
     security(
         ("api_key" = [])
     ),
     tag = "Session Management"
 )]
-// List all available sessions
+// List all available sessions, optionally limited to a modified time range
 async fn list_sessions(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-) -> Result<Json<SessionListResponse>, StatusCode> {
+    Query(query): Query<ListSessionsQuery>,
+) -> Result<Json<SessionListResponse>, SessionErrorResponse> {
     verify_secret_key(&headers, &state)?;
 
-    let sessions = get_valid_sorted_sessions(SortOrder::Descending)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let range = query.modified_range()?;
+    let sessions = state
+        .session_store
+        .list(SortOrder::Descending, range)
+        .map_err(|e| SessionErrorResponse::new(SessionErrorCode::Internal, e.to_string()))?;
 
     Ok(Json(SessionListResponse { sessions }))
 }
@@ -98,13 +375,16 @@ async fn list_sessions(
     get,
     path = "/sessions/{session_id}",
     params(
-        ("session_id" = String, Path, description = "Unique identifier for the session")
+        ("session_id" = String, Path, description = "Unique identifier for the session"),
+        SessionHistoryQuery
     ),
     responses(
         (status = 200, description = "Session history retrieved successfully", body = SessionHistoryResponse),
-        (status = 401, description = "Unauthorized - Invalid or missing API key"),
-        (status = 404, description = "Session not found"),
-        (status = 500, description = "Internal server error")
+        (status = 304, description = "Session unchanged since the ETag given in If-None-Match"),
+        (status = 400, description = "Bad request - Invalid session ID or message range", body = SessionErrorResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 404, description = "Session not found", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
     ),
     security(
         ("api_key" = [])
@@ -116,38 +396,146 @@ async fn get_session_history(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(session_id): Path<String>,
-) -> Result<Json<SessionHistoryResponse>, StatusCode> {
+    Query(query): Query<SessionHistoryQuery>,
+) -> Result<Response, SessionErrorResponse> {
     verify_secret_key(&headers, &state)?;
 
-    let session_path = match session::get_path(session::Identifier::Name(session_id.clone())) {
-        Ok(path) => path,
-        Err(_) => return Err(StatusCode::BAD_REQUEST),
-    };
+    // Answer polls for an unchanged session without reading or re-sending its messages
+    let store = &state.session_store;
+    ensure_session_exists(store, &session_id)?;
+    let etag = store
+        .revision(&session_id)
+        .map(|revision| session_etag(&revision, &query));
+    if let Some(etag) = &etag {
+        if if_none_match(&headers, etag) {
+            return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag.clone())]).into_response());
+        }
+    }
+
+    let mut response = Json(load_session_history(store, session_id, &query)?).into_response();
+    if let Some(etag) = etag.and_then(|etag| HeaderValue::from_str(&etag).ok()) {
+        response.headers_mut().insert(ETAG, etag);
+    }
+    Ok(response)
+}
 
-    let metadata = session::read_metadata(&session_path).map_err(|_| StatusCode::NOT_FOUND)?;
+fn ensure_session_exists(
+    store: &SessionStore,
+    session_id: &str,
+) -> Result<(), SessionErrorResponse> {
+    let exists = store
+        .exists(session_id)
+        .map_err(|_| SessionErrorResponse::invalid_session_id(session_id))?;
 
-    let messages = match session::read_messages(&session_path) {
+    if !exists {
+        return Err(SessionErrorResponse::not_found(session_id));
+    }
+    Ok(())
+}
+
+/// Weak ETag for a slice of a session's history, derived from the session's revision so
+/// it can be computed without reading the messages
+fn session_etag(revision: &str, query: &SessionHistoryQuery) -> String {
+    let bound = |b: Option<usize>| b.map_or_else(String::new, |b| b.to_string());
+    format!(
+        "W/\"{}-{}-{}\"",
+        revision,
+        bound(query.from),
+        bound(query.to)
+    )
+}
+
+// Whether the request's If-None-Match header lists `etag`, using weak comparison
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+}
+
+// Read a session's metadata and the requested slice of its messages
+fn load_session_history(
+    store: &SessionStore,
+    session_id: String,
+    query: &SessionHistoryQuery,
+) -> Result<SessionHistoryResponse, SessionErrorResponse> {
+    let metadata = store.read_metadata(&session_id).map_err(|e| {
+        SessionErrorResponse::new(
+            SessionErrorCode::SessionUnreadable,
+            format!("Failed to read session metadata: {}", e),
+        )
+    })?;
+
+    let messages = match store.read_messages(&session_id) {
         Ok(messages) => messages,
         Err(e) => {
             error!("Failed to read session messages: {:?}", e);
-            return Err(StatusCode::NOT_FOUND);
+            return Err(SessionErrorResponse::new(
+                SessionErrorCode::SessionUnreadable,
+                format!("Failed to read session messages: {}", e),
+            ));
         }
     };
 
-    Ok(Json(SessionHistoryResponse {
+    let total_messages = messages.len();
+    let range = query.message_range(total_messages)?;
+
+    Ok(SessionHistoryResponse {
         session_id,
         metadata,
-        messages: messages.messages().clone(),
-    }))
+        messages: messages.messages()[range].to_vec(),
+        total_messages,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/latest",
+    params(SessionHistoryQuery),
+    responses(
+        (status = 200, description = "Most recently modified session retrieved successfully", body = SessionHistoryResponse),
+        (status = 400, description = "Bad request - Invalid message range", body = SessionErrorResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 404, description = "No sessions found", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    tag = "Session Management"
+)]
+// Get the history of the most recently modified session, for one-shot resume
+async fn get_latest_session(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<SessionHistoryQuery>,
+) -> Result<Json<SessionHistoryResponse>, SessionErrorResponse> {
+    verify_secret_key(&headers, &state)?;
+
+    let latest = state
+        .session_store
+        .list(SortOrder::Descending, ModifiedRange::default())
+        .map_err(|e| SessionErrorResponse::new(SessionErrorCode::Internal, e.to_string()))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            SessionErrorResponse::new(SessionErrorCode::SessionNotFound, "No sessions found")
+        })?;
+
+    load_session_history(&state.session_store, latest.id, &query).map(Json)
 }
 
 #[utoipa::path(
     get,
     path = "/sessions/insights",
+    params(SessionInsightsQuery),
     responses(
         (status = 200, description = "Session insights retrieved successfully", body = SessionInsights),
-        (status = 401, description = "Unauthorized - Invalid or missing API key"),
-        (status = 500, description = "Internal server error")
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
     ),
     security(
         ("api_key" = [])
@@ -157,15 +545,38 @@ async fn get_session_history(
 async fn get_session_insights(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-) -> Result<Json<SessionInsights>, StatusCode> {
+    Query(query): Query<SessionInsightsQuery>,
+) -> Result<Json<SessionInsights>, SessionErrorResponse> {
     info!("Received request for session insights");
 
     verify_secret_key(&headers, &state)?;
 
-    let sessions = get_valid_sorted_sessions(SortOrder::Descending).map_err(|e| {
-        error!("Failed to get session info: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let mut sessions = state
+        .session_store
+        .list(SortOrder::Descending, ModifiedRange::default())
+        .map_err(|e| {
+            error!("Failed to get session info: {:?}", e);
+            SessionErrorResponse::new(SessionErrorCode::Internal, e.to_string())
+        })?;
+
+    // Describe unnamed sessions from their first message so they are counted too. The
+    // provider is never used here, so insights doesn't wait on one call per session.
+    if auto_describe_sessions() {
+        for session in sessions
+            .iter_mut()
+            .filter(|session| session.metadata.description.is_empty())
+        {
+            match state
+                .session_store
+                .describe_if_missing(&session.id, None)
+                .await
+            {
+                Ok(Some(description)) => session.metadata.description = description,
+                Ok(None) => {}
+                Err(e) => error!("Failed to describe session {}: {:?}", session.id, e),
+            }
+        }
+    }
 
     // Filter out sessions without descriptions
     let sessions: Vec<SessionInfo> = sessions
@@ -188,45 +599,33 @@ async fn get_session_insights(
     let mut total_duration = 0.0;
     let mut total_tokens = 0;
     let mut activity_by_date: HashMap<String, usize> = HashMap::new();
+    let idle_secs = i64::from(query.idle_minutes.unwrap_or(DEFAULT_IDLE_SESSION_MINUTES)) * 60;
+    let now = Utc::now().timestamp();
+    let mut idle_sessions = 0;
+    let project_roots = insights_project_roots();
 
     for session in &sessions {
-        // Track directory usage
-        let dir = session.metadata.working_dir.to_string_lossy().to_string();
+        if session.metadata.is_idle(now, idle_secs) {
+            idle_sessions += 1;
+        }
+
+        // Track directory usage, rolled up to the configured project roots
+        let dir = project_dir(&session.metadata.working_dir, &project_roots);
         *dir_counts.entry(dir).or_insert(0) += 1;
 
-        // Track tokens - only add positive values to prevent negative totals
-        if let Some(tokens) = session.metadata.accumulated_total_tokens {
-            match tokens.cmp(&0) {
-                std::cmp::Ordering::Greater => {
-                    total_tokens += tokens as i64;
-                }
-                std::cmp::Ordering::Less => {
-                    // Log negative token values for debugging
-                    info!(
-                        "Warning: Session {} has negative accumulated_total_tokens: {}",
-                        session.id, tokens
-                    );
-                }
-                std::cmp::Ordering::Equal => {
-                    // Zero tokens, no action needed
-                }
-            }
-        }
+        total_tokens += session_tokens(session);
 
         // Track activity by date
-        if let Ok(date) = DateTime::parse_from_str(&session.modified, "%Y-%m-%d %H:%M:%S UTC") {
+        if let Some(date) = modified_date(session) {
             let date_str = date.format("%Y-%m-%d").to_string();
             *activity_by_date.entry(date_str).or_insert(0) += 1;
         }
 
         // Calculate session duration from messages
-        let session_path = session::get_path(session::Identifier::Name(session.id.clone()));
-        if let Ok(session_path) = session_path {
-            if let Ok(messages) = session::read_messages(&session_path) {
-                if let (Some(first), Some(last)) = (messages.first(), messages.last()) {
-                    let duration = (last.created - first.created) as f64 / 60.0; // Convert to minutes
-                    total_duration += duration;
-                }
+        if let Ok(messages) = state.session_store.read_messages(&session.id) {
+            if let (Some(first), Some(last)) = (messages.first(), messages.last()) {
+                let duration = (last.created - first.created) as f64 / 60.0; // Convert to minutes
+                total_duration += duration;
             }
         }
     }
@@ -254,12 +653,175 @@ async fn get_session_insights(
         avg_session_duration,
         total_tokens,
         recent_activity,
+        idle_sessions,
     };
 
     info!("Returning insights: {:?}", insights);
     Ok(Json(insights))
 }
 
+/// Tokens a session has used. Only positive values count, so a corrupt negative total
+/// can't drag down the sum.
+fn session_tokens(session: &SessionInfo) -> i64 {
+    match session.metadata.accumulated_total_tokens {
+        Some(tokens) if tokens > 0 => tokens as i64,
+        Some(tokens) if tokens < 0 => {
+            // Log negative token values for debugging
+            info!(
+                "Warning: Session {} has negative accumulated_total_tokens: {}",
+                session.id, tokens
+            );
+            0
+        }
+        _ => 0,
+    }
+}
+
+/// The day a session was last modified, if its modified time can be parsed. The time is
+/// written in UTC with a literal `UTC` suffix rather than an offset, so it is parsed as a
+/// naive date time.
+fn modified_date(session: &SessionInfo) -> Option<NaiveDate> {
+    NaiveDateTime::parse_from_str(&session.modified, "%Y-%m-%d %H:%M:%S UTC")
+        .ok()
+        .map(|time| time.date())
+}
+
+/// Sum token usage per day for the `days` days ending on `today`, oldest first. Days
+/// without sessions are included with zero usage so the series has no gaps.
+fn token_usage_by_day(
+    sessions: &[SessionInfo],
+    days: u32,
+    today: NaiveDate,
+) -> Vec<DailyTokenUsage> {
+    let first_day = today - chrono::Days::new(u64::from(days.saturating_sub(1)));
+    let mut usage: Vec<DailyTokenUsage> = first_day
+        .iter_days()
+        .take(days as usize)
+        .map(|date| DailyTokenUsage {
+            date: date.format("%Y-%m-%d").to_string(),
+            total_tokens: 0,
+            sessions: 0,
+        })
+        .collect();
+
+    for session in sessions {
+        let Some(date) = modified_date(session) else {
+            continue;
+        };
+        if date < first_day || date > today {
+            continue;
+        }
+        let day = &mut usage[(date - first_day).num_days() as usize];
+        day.total_tokens += session_tokens(session);
+        day.sessions += 1;
+    }
+    usage
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/token-usage",
+    params(TokenUsageQuery),
+    responses(
+        (status = 200, description = "Daily token usage retrieved successfully", body = TokenUsageResponse),
+        (status = 400, description = "Bad request - Invalid number of days", body = SessionErrorResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    tag = "Session Management"
+)]
+// Token usage per day, bucketed by the day each session was last modified
+async fn get_token_usage(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<TokenUsageQuery>,
+) -> Result<Json<TokenUsageResponse>, SessionErrorResponse> {
+    verify_secret_key(&headers, &state)?;
+
+    let days = query.days.unwrap_or(DEFAULT_TOKEN_USAGE_DAYS);
+    if days == 0 || days > MAX_TOKEN_USAGE_DAYS {
+        return Err(SessionErrorResponse::new(
+            SessionErrorCode::InvalidRequest,
+            format!(
+                "Invalid days {}, expected a value from 1 to {}",
+                days, MAX_TOKEN_USAGE_DAYS
+            ),
+        ));
+    }
+
+    let today = Utc::now().date_naive();
+    let first_day = today - chrono::Days::new(u64::from(days - 1));
+    let range = ModifiedRange {
+        since: Some(first_day.and_time(NaiveTime::MIN).and_utc()),
+        until: None,
+    };
+    let sessions = state
+        .session_store
+        .list(SortOrder::Descending, range)
+        .map_err(|e| {
+            error!("Failed to get session info: {:?}", e);
+            SessionErrorResponse::new(SessionErrorCode::Internal, e.to_string())
+        })?;
+
+    let days = token_usage_by_day(&sessions, days, today);
+    let total_tokens = days.iter().map(|day| day.total_tokens).sum();
+    Ok(Json(TokenUsageResponse { days, total_tokens }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/sessions/{session_id}/describe",
+    params(
+        ("session_id" = String, Path, description = "Unique identifier for the session"),
+        DescribeSessionQuery
+    ),
+    responses(
+        (status = 200, description = "Missing description generated, or the session left unchanged", body = DescribeSessionResponse),
+        (status = 400, description = "Bad request - Invalid session ID", body = SessionErrorResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 404, description = "Session not found", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    tag = "Session Management"
+)]
+// Generate a description for a session that doesn't have one
+async fn describe_session(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(session_id): Path<String>,
+    Query(query): Query<DescribeSessionQuery>,
+) -> Result<Json<DescribeSessionResponse>, SessionErrorResponse> {
+    verify_secret_key(&headers, &state)?;
+
+    ensure_session_exists(&state.session_store, &session_id)?;
+
+    let provider = if query.use_provider.unwrap_or(false) {
+        // Fall back to the heuristic when no provider has been configured yet
+        state.get_agent().await.provider().await.ok()
+    } else {
+        None
+    };
+
+    let description = state
+        .session_store
+        .describe_if_missing(&session_id, provider)
+        .await
+        .map_err(|e| {
+            SessionErrorResponse::new(
+                SessionErrorCode::Internal,
+                format!("Failed to describe session: {}", e),
+            )
+        })?;
+
+    Ok(Json(DescribeSessionResponse { description }))
+}
+
 #[utoipa::path(
     put,
     path = "/sessions/{session_id}/metadata",
@@ -269,10 +831,10 @@ async fn get_session_insights(
     ),
     responses(
         (status = 200, description = "Session metadata updated successfully"),
-        (status = 400, description = "Bad request - Description too long (max 200 characters)"),
-        (status = 401, description = "Unauthorized - Invalid or missing API key"),
-        (status = 404, description = "Session not found"),
-        (status = 500, description = "Internal server error")
+        (status = 400, description = "Bad request - Description too long (max 200 characters)", body = SessionErrorResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 404, description = "Session not found", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
     ),
     security(
         ("api_key" = [])
@@ -285,27 +847,36 @@ async fn update_session_metadata(
     headers: HeaderMap,
     Path(session_id): Path<String>,
     Json(request): Json<UpdateSessionMetadataRequest>,
-) -> Result<StatusCode, StatusCode> {
+) -> Result<StatusCode, SessionErrorResponse> {
     verify_secret_key(&headers, &state)?;
 
     // Validate description length
     if request.description.len() > MAX_DESCRIPTION_LENGTH {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(SessionErrorResponse::new(
+            SessionErrorCode::InvalidRequest,
+            format!(
+                "Description is too long (max {} characters)",
+                MAX_DESCRIPTION_LENGTH
+            ),
+        ));
     }
 
-    let session_path = session::get_path(session::Identifier::Name(session_id.clone()))
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
-
-    // Read current metadata
-    let mut metadata = session::read_metadata(&session_path).map_err(|_| StatusCode::NOT_FOUND)?;
+    ensure_session_exists(&state.session_store, &session_id)?;
 
-    // Update description
-    metadata.description = request.description;
-
-    // Save updated metadata
-    session::update_metadata(&session_path, &metadata)
+    // Update the description under the session's metadata lock so token counts the
+    // agent records at the same time aren't lost
+    state
+        .session_store
+        .modify_metadata(&session_id, |metadata| {
+            metadata.description = request.description;
+        })
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| {
+            SessionErrorResponse::new(
+                SessionErrorCode::Internal,
+                format!("Failed to save session metadata: {}", e),
+            )
+        })?;
 
     Ok(StatusCode::OK)
 }
@@ -318,9 +889,9 @@ async fn update_session_metadata(
     ),
     responses(
         (status = 200, description = "Session deleted successfully"),
-        (status = 401, description = "Unauthorized - Invalid or missing API key"),
-        (status = 404, description = "Session not found"),
-        (status = 500, description = "Internal server error")
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 404, description = "Session not found", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
     ),
     security(
         ("api_key" = [])
@@ -332,43 +903,211 @@ async fn delete_session(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(session_id): Path<String>,
-) -> Result<StatusCode, StatusCode> {
+) -> Result<StatusCode, SessionErrorResponse> {
     verify_secret_key(&headers, &state)?;
 
-    // Get the session path
-    let session_path = match session::get_path(session::Identifier::Name(session_id.clone())) {
-        Ok(path) => path,
-        Err(_) => return Err(StatusCode::BAD_REQUEST),
-    };
-
-    // Check if session file exists
-    if !session_path.exists() {
-        return Err(StatusCode::NOT_FOUND);
+    // Validate the session id before touching the store
+    if state.session_store.path(&session_id).is_err() {
+        return Err(SessionErrorResponse::invalid_session_id(&session_id));
     }
 
-    // Delete the session file
-    std::fs::remove_file(&session_path).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Delete the session, notifying anything that caches session data
+    let deleted = state.session_store.delete(&session_id).map_err(|e| {
+        SessionErrorResponse::new(
+            SessionErrorCode::Internal,
+            format!("Failed to delete session: {}", e),
+        )
+    })?;
+
+    if !deleted {
+        return Err(SessionErrorResponse::not_found(&session_id));
+    }
 
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    post,
+    path = "/sessions/import",
+    request_body(
+        content = String,
+        description = "Exported session, either as session JSONL or as a JSON object with metadata and messages",
+        content_type = "text/plain"
+    ),
+    responses(
+        (status = 200, description = "Session imported successfully", body = SessionInfo),
+        (status = 400, description = "Bad request - Malformed or oversized transcript", body = SessionErrorResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key", body = SessionErrorResponse),
+        (status = 500, description = "Internal server error", body = SessionErrorResponse)
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    tag = "Session Management"
+)]
+// Import an exported transcript as a new session
+async fn import_session(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<SessionInfo>, SessionErrorResponse> {
+    verify_secret_key(&headers, &state)?;
+
+    let (metadata, conversation) = session::parse_session_import(&body)
+        .map_err(|e| SessionErrorResponse::new(SessionErrorCode::InvalidRequest, e.to_string()))?;
+
+    let (session_id, session_path) = state
+        .session_store
+        .import(&metadata, &conversation)
+        .map_err(|e| {
+            SessionErrorResponse::new(
+                SessionErrorCode::Internal,
+                format!("Failed to import session: {}", e),
+            )
+        })?;
+
+    info!("Imported session {}", session_id);
+
+    Ok(Json(SessionInfo {
+        id: session_id,
+        path: session_path.to_string_lossy().to_string(),
+        modified: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        metadata,
+    }))
+}
+
 // Configure routes for this module
 pub fn routes(state: Arc<AppState>) -> Router {
+    // Insights reads every session's messages, so it gets a much tighter budget
+    let session_limiter = RateLimiter::per_minute_from_config(
+        "GOOSE_SERVER_SESSION_RATE_LIMIT",
+        DEFAULT_SESSION_RATE_LIMIT,
+    );
+    let insights_limiter = RateLimiter::per_minute_from_config(
+        "GOOSE_SERVER_INSIGHTS_RATE_LIMIT",
+        DEFAULT_INSIGHTS_RATE_LIMIT,
+    );
+
+    let insights = Router::new()
+        .route("/sessions/insights", get(get_session_insights))
+        .route_layer(middleware::from_fn_with_state(insights_limiter, rate_limit));
+
     Router::new()
         .route("/sessions", get(list_sessions))
+        .route("/sessions/latest", get(get_latest_session))
+        .route("/sessions/token-usage", get(get_token_usage))
+        .route(
+            "/sessions/import",
+            // Leave headroom over the import limit so oversized payloads get a JSON error
+            post(import_session).layer(DefaultBodyLimit::max(session::MAX_IMPORT_SIZE + 1024)),
+        )
         .route("/sessions/{session_id}", get(get_session_history))
         .route("/sessions/{session_id}/delete", delete(delete_session))
-        .route("/sessions/insights", get(get_session_insights))
         .route(
             "/sessions/{session_id}/metadata",
             put(update_session_metadata),
         )
+        .route("/sessions/{session_id}/describe", post(describe_session))
+        .route_layer(middleware::from_fn_with_state(session_limiter, rate_limit))
+        .merge(insights)
         .with_state(state)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use goose::agents::Agent;
+    use goose::conversation::Conversation;
+    use goose::session::backend::{file_revision, MemorySessionBackend, SessionBackend};
+    use tower::ServiceExt;
+
+    async fn get_json(
+        backend: Arc<MemorySessionBackend>,
+        uri: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let state = AppState::with_session_store(
+            Arc::new(Agent::new()),
+            "test-secret".to_string(),
+            SessionStore::with_backend(backend),
+        );
+        let request = Request::builder()
+            .uri(uri)
+            .header("x-secret-key", "test-secret")
+            .body(Body::empty())
+            .unwrap();
+        let response = routes(state).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_session() {
+        let backend = Arc::new(MemorySessionBackend::new());
+        let (status, _) = get_json(backend.clone(), "/sessions/latest").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let conversation = Conversation::new_unvalidated(vec![Message::user().with_text("hi")]);
+        backend
+            .write("my-session", &SessionMetadata::default(), &conversation)
+            .unwrap();
+        let (status, body) = get_json(backend, "/sessions/latest").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["sessionId"], "my-session");
+        assert_eq!(body["totalMessages"], 1);
+    }
+
+    #[test]
+    fn test_route_names_are_reserved() {
+        // A session with one of these names would be hidden by the static route
+        for name in ["latest", "insights", "token-usage", "import"] {
+            assert!(session::RESERVED_SESSION_NAMES.contains(&name));
+            assert!(session::validate_new_session_name(name).is_err());
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_imports_get_their_own_sessions() {
+        let backend = Arc::new(MemorySessionBackend::new());
+        let state = AppState::with_session_store(
+            Arc::new(Agent::new()),
+            "test-secret".to_string(),
+            SessionStore::with_backend(backend.clone()),
+        );
+        let app = routes(state);
+        let body = serde_json::json!({
+            "metadata": SessionMetadata::default(),
+            "messages": [Message::user().with_text("hi")],
+        })
+        .to_string();
+
+        let imports = (0..4).map(|_| {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/sessions/import")
+                .header("x-secret-key", "test-secret")
+                .body(Body::from(body.clone()))
+                .unwrap();
+            tokio::spawn(app.clone().oneshot(request))
+        });
+        let mut ids = Vec::new();
+        for import in imports.collect::<Vec<_>>() {
+            let response = import.await.unwrap().unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let session: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            ids.push(session["id"].as_str().unwrap().to_string());
+        }
+
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+        for id in &ids {
+            assert!(backend.exists(id).unwrap());
+        }
+    }
 
     #[tokio::test]
     async fn test_update_session_metadata_request_deserialization() {
@@ -422,4 +1161,221 @@ mod tests {
         assert!(String::new().len() <= MAX_DESCRIPTION_LENGTH); // Empty string
         assert!("Short".len() <= MAX_DESCRIPTION_LENGTH); // Short string
     }
+
+    #[test]
+    fn test_session_etag_and_if_none_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(&path, "{}\n").unwrap();
+
+        let all = SessionHistoryQuery {
+            from: None,
+            to: None,
+        };
+        let tail = SessionHistoryQuery {
+            from: Some(2),
+            to: None,
+        };
+        let etag = session_etag(&file_revision(&path).unwrap(), &all);
+        assert!(etag.starts_with("W/\""));
+        assert_eq!(session_etag(&file_revision(&path).unwrap(), &all), etag);
+        assert_ne!(session_etag(&file_revision(&path).unwrap(), &tail), etag);
+
+        // Appending a message changes the file size and so the tag
+        std::fs::write(&path, "{}\n{\"role\":\"user\"}\n").unwrap();
+        assert_ne!(session_etag(&file_revision(&path).unwrap(), &all), etag);
+
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, &etag));
+        headers.insert(
+            IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"other\", {}", etag.trim_start_matches("W/")))
+                .unwrap(),
+        );
+        assert!(if_none_match(&headers, &etag));
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, &etag));
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"other\""));
+        assert!(!if_none_match(&headers, &etag));
+    }
+
+    #[test]
+    fn test_list_sessions_query_modified_range() {
+        let query: ListSessionsQuery = serde_json::from_str(
+            r#"{"since": "2025-01-07", "until": "2025-01-07T18:30:00+02:00"}"#,
+        )
+        .unwrap();
+        let range = query.modified_range().unwrap();
+        assert_eq!(
+            range.since.unwrap().to_rfc3339(),
+            "2025-01-07T00:00:00+00:00"
+        );
+        assert_eq!(
+            range.until.unwrap().to_rfc3339(),
+            "2025-01-07T16:30:00+00:00"
+        );
+
+        let date_only_until: ListSessionsQuery =
+            serde_json::from_str(r#"{"until": "2025-01-07"}"#).unwrap();
+        let range = date_only_until.modified_range().unwrap();
+        assert!(range.since.is_none());
+        assert_eq!(
+            range.until.unwrap().to_rfc3339(),
+            "2025-01-07T23:59:59+00:00"
+        );
+
+        let invalid: ListSessionsQuery =
+            serde_json::from_str(r#"{"since": "last tuesday"}"#).unwrap();
+        assert_eq!(
+            invalid.modified_range().unwrap_err().code,
+            SessionErrorCode::InvalidRequest
+        );
+
+        let empty: ListSessionsQuery = serde_json::from_str("{}").unwrap();
+        assert!(empty.modified_range().unwrap().is_unbounded());
+    }
+
+    #[test]
+    fn test_session_history_query_message_range() {
+        let query = |from, to| SessionHistoryQuery { from, to };
+
+        assert_eq!(query(None, None).message_range(10).unwrap(), 0..10);
+        assert_eq!(query(Some(2), Some(5)).message_range(10).unwrap(), 2..5);
+        assert_eq!(query(None, Some(4)).message_range(10).unwrap(), 0..4);
+
+        // Out of range bounds are clamped to the available messages
+        assert_eq!(query(Some(8), Some(50)).message_range(10).unwrap(), 8..10);
+        assert_eq!(query(Some(20), None).message_range(10).unwrap(), 10..10);
+        assert_eq!(query(None, None).message_range(0).unwrap(), 0..0);
+
+        assert_eq!(
+            query(Some(5), Some(2)).message_range(10).unwrap_err().code,
+            SessionErrorCode::InvalidRequest
+        );
+    }
+
+    #[test]
+    fn test_session_error_response_body_and_status() {
+        let response = SessionErrorResponse::not_found("20250101_120000");
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "code": "session_not_found",
+                "message": "Session '20250101_120000' not found"
+            })
+        );
+        assert_eq!(response.into_response().status(), StatusCode::NOT_FOUND);
+
+        let unauthorized = SessionErrorResponse::from(StatusCode::UNAUTHORIZED);
+        assert_eq!(unauthorized.code, SessionErrorCode::Unauthorized);
+        assert_eq!(
+            unauthorized.into_response().status(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        assert_eq!(
+            SessionErrorResponse::invalid_session_id("../x")
+                .into_response()
+                .status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn test_modified_date() {
+        let session = |modified: &str| SessionInfo {
+            id: "session".to_string(),
+            path: String::new(),
+            modified: modified.to_string(),
+            metadata: SessionMetadata::default(),
+        };
+        assert_eq!(
+            modified_date(&session("2025-01-07 18:30:00 UTC")),
+            NaiveDate::from_ymd_opt(2025, 1, 7)
+        );
+        assert_eq!(modified_date(&session("Unknown")), None);
+        assert_eq!(modified_date(&session("2025-01-07T18:30:00Z")), None);
+    }
+
+    #[tokio::test]
+    async fn test_insights_recent_activity() {
+        let backend = Arc::new(MemorySessionBackend::new());
+        let metadata = SessionMetadata {
+            description: "My session".to_string(),
+            ..SessionMetadata::default()
+        };
+        backend
+            .write("my-session", &metadata, &Conversation::default())
+            .unwrap();
+
+        // Insights only counts described sessions
+        let (status, body) = get_json(backend, "/sessions/insights").await;
+        assert_eq!(status, StatusCode::OK);
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        assert_eq!(body["recentActivity"], serde_json::json!([[today, 1]]));
+    }
+
+    #[test]
+    fn test_token_usage_by_day() {
+        let session = |modified: &str, tokens| SessionInfo {
+            id: modified.to_string(),
+            path: String::new(),
+            modified: modified.to_string(),
+            metadata: SessionMetadata {
+                accumulated_total_tokens: tokens,
+                ..SessionMetadata::default()
+            },
+        };
+        let sessions = vec![
+            session("2025-01-07 18:30:00 UTC", Some(100)),
+            session("2025-01-07 09:00:00 UTC", Some(50)),
+            session("2025-01-05 12:00:00 UTC", Some(-10)),
+            session("2025-01-01 12:00:00 UTC", Some(1000)),
+            session("Unknown", Some(1000)),
+        ];
+        let today = NaiveDate::from_ymd_opt(2025, 1, 7).unwrap();
+
+        let usage = token_usage_by_day(&sessions, 3, today);
+        let day = |date: &str, total_tokens, sessions| DailyTokenUsage {
+            date: date.to_string(),
+            total_tokens,
+            sessions,
+        };
+        assert_eq!(
+            usage,
+            vec![
+                day("2025-01-05", 0, 1),
+                day("2025-01-06", 0, 0),
+                day("2025-01-07", 150, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_dir_rolls_up_to_roots() {
+        let roots = vec![
+            PathBuf::from("/home/me/proj"),
+            PathBuf::from("/home/me/proj/web"),
+        ];
+
+        assert_eq!(
+            project_dir(std::path::Path::new("/home/me/proj/api"), &roots),
+            "/home/me/proj"
+        );
+        assert_eq!(
+            project_dir(std::path::Path::new("/home/me/proj"), &roots),
+            "/home/me/proj"
+        );
+        // The most specific root wins
+        assert_eq!(
+            project_dir(std::path::Path::new("/home/me/proj/web/src"), &roots),
+            "/home/me/proj/web"
+        );
+        // Roots match whole path components only
+        assert_eq!(
+            project_dir(std::path::Path::new("/home/me/project2"), &roots),
+            "/home/me/project2"
+        );
+        assert_eq!(project_dir(std::path::Path::new("/tmp"), &[]), "/tmp");
+    }
 }
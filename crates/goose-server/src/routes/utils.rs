@@ -1,10 +1,15 @@
 use crate::state::AppState;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
 use goose::config::Config;
 use goose::providers::base::{ConfigKey, ProviderMetadata};
-use http::{HeaderMap, StatusCode};
+use http::{HeaderMap, HeaderValue, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum KeyLocation {
@@ -37,6 +42,48 @@ pub fn verify_secret_key(headers: &HeaderMap, state: &AppState) -> Result<Status
     }
 }
 
+/// Whether requests from loopback addresses may skip secret key verification, set with
+/// GOOSE_SERVER_ALLOW_LOCAL_NOAUTH. Off by default.
+///
+/// Turning this on trusts every process on the machine: any local program that connects
+/// over loopback without an `Origin` header is treated as if it held the secret key,
+/// whichever user runs it. Browsers send `Origin` with cross-origin fetches and POSTs, so
+/// web pages still need the key for those; the plain GETs a page can trigger through links
+/// or embeds skip it, but their responses can't be read by the page. Only enable this on
+/// single-user machines.
+pub fn allow_local_noauth() -> bool {
+    Config::global()
+        .get_param("GOOSE_SERVER_ALLOW_LOCAL_NOAUTH")
+        .unwrap_or(false)
+}
+
+/// Middleware that authenticates requests whose connection comes from a loopback address
+/// by supplying the server's secret key, so `verify_secret_key` accepts them.
+///
+/// The caller is identified by the connection's remote address, never by headers, and
+/// requests without connection info are left untouched. Requests carrying an `Origin`
+/// header come from a browser, where any page the user visits can reach loopback, so
+/// they still need the secret key.
+pub async fn authenticate_loopback_callers(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let is_loopback = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_some_and(|ConnectInfo(addr)| addr.ip().is_loopback());
+    let from_browser = request.headers().contains_key(http::header::ORIGIN);
+
+    if is_loopback && !from_browser {
+        if let Ok(secret_key) = HeaderValue::from_str(&state.secret_key) {
+            request.headers_mut().insert("X-Secret-Key", secret_key);
+        }
+    }
+
+    next.run(request).await
+}
+
 /// Inspects a configuration key to determine if it's set, its location, and value (for non-secret keys)
 #[allow(dead_code)]
 pub fn inspect_key(key_name: &str, is_secret: bool) -> Result<KeyInfo, Box<dyn Error>> {
@@ -176,3 +223,74 @@ pub fn check_provider_configured(metadata: &ProviderMetadata) -> bool {
         is_set_in_env || is_set_in_config
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn protected(
+        State(state): State<Arc<AppState>>,
+        headers: HeaderMap,
+    ) -> Result<StatusCode, StatusCode> {
+        verify_secret_key(&headers, &state)
+    }
+
+    async fn status_from(remote: Option<&str>) -> StatusCode {
+        status_with_origin(remote, None).await
+    }
+
+    async fn status_with_origin(remote: Option<&str>, origin: Option<&str>) -> StatusCode {
+        let state = AppState::new(
+            Arc::new(goose::agents::Agent::new()),
+            "test-secret".to_string(),
+        );
+        let app = Router::new()
+            .route("/protected", get(protected))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                authenticate_loopback_callers,
+            ))
+            .with_state(state);
+
+        let mut request = http::Request::builder()
+            .uri("/protected")
+            .body(Body::empty())
+            .unwrap();
+        if let Some(origin) = origin {
+            request
+                .headers_mut()
+                .insert(http::header::ORIGIN, HeaderValue::from_str(origin).unwrap());
+        }
+        if let Some(remote) = remote {
+            let addr: SocketAddr = remote.parse().unwrap();
+            request.extensions_mut().insert(ConnectInfo(addr));
+        }
+
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_loopback_callers_skip_secret_key() {
+        assert_eq!(status_from(Some("127.0.0.1:50000")).await, StatusCode::OK);
+        assert_eq!(status_from(Some("[::1]:50000")).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_remote_or_unknown_callers_still_need_secret_key() {
+        assert_eq!(
+            status_from(Some("192.168.1.20:50000")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(status_from(None).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_loopback_browser_requests_still_need_secret_key() {
+        assert_eq!(
+            status_with_origin(Some("127.0.0.1:50000"), Some("https://example.com")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use goose::agents::Agent;
 use goose::scheduler_trait::SchedulerTrait;
+use goose::session::SessionStore;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
@@ -16,16 +17,27 @@ pub struct AppState {
     pub scheduler: Arc<RwLock<Option<Arc<dyn SchedulerTrait>>>>,
     pub recipe_file_hash_map: Arc<Mutex<HashMap<String, PathBuf>>>,
     pub session_counter: Arc<AtomicUsize>,
+    pub session_store: SessionStore,
 }
 
 impl AppState {
     pub fn new(agent: AgentRef, secret_key: String) -> Arc<AppState> {
+        Self::with_session_store(agent, secret_key, SessionStore::new())
+    }
+
+    /// State whose session routes read and write through `session_store`
+    pub fn with_session_store(
+        agent: AgentRef,
+        secret_key: String,
+        session_store: SessionStore,
+    ) -> Arc<AppState> {
         Arc::new(Self {
             agent: Arc::new(RwLock::new(agent)),
             secret_key,
             scheduler: Arc::new(RwLock::new(None)),
             recipe_file_hash_map: Arc::new(Mutex::new(HashMap::new())),
             session_counter: Arc::new(AtomicUsize::new(0)),
+            session_store,
         })
     }
 
@@ -52,7 +64,11 @@ impl AppState {
     }
 
     pub async fn reset(&self) {
+        let new_agent = Agent::new();
+        new_agent
+            .set_session_store(self.session_store.clone())
+            .await;
         let mut agent = self.agent.write().await;
-        *agent = Arc::new(Agent::new());
+        *agent = Arc::new(new_agent);
     }
 }
@@ -27,10 +27,14 @@ use crate::agents::subagent_execution_tool::subagent_execute_task_tool::{
     self, SUBAGENT_EXECUTE_TASK_TOOL_NAME,
 };
 use crate::agents::subagent_execution_tool::tasks_manager::TasksManager;
+use crate::agents::subagent_execution_tool::transcript::TranscriptStore;
 use crate::agents::tool_route_manager::ToolRouteManager;
 use crate::agents::tool_router_index_manager::ToolRouterIndexManager;
 use crate::agents::types::SessionConfig;
 use crate::agents::types::{FrontendTool, ToolResultReceiver};
+use crate::agents::usage_alert::{
+    usage_alert_notification, UsageThresholds, USAGE_ALERT_NOTIFICATION_TYPE,
+};
 use crate::config::{Config, ExtensionConfigManager, PermissionManager};
 use crate::context_mgmt::auto_compact;
 use crate::conversation::{debug_conversation_fix, fix_conversation, Conversation};
@@ -38,10 +42,12 @@ use crate::permission::permission_judge::{check_tool_permissions, PermissionChec
 use crate::permission::PermissionConfirmation;
 use crate::providers::base::Provider;
 use crate::providers::errors::ProviderError;
+use crate::providers::pricing::get_model_pricing;
 use crate::recipe::{Author, Recipe, Response, Settings, SubRecipe};
 use crate::scheduler_trait::SchedulerTrait;
 use crate::session;
 use crate::session::extension_data::ExtensionState;
+use crate::session::SessionStore;
 use crate::tool_monitor::{ToolCall, ToolMonitor};
 use crate::utils::is_token_cancelled;
 use mcp_core::ToolResult;
@@ -80,11 +86,22 @@ pub struct ReplyContext {
 pub struct ToolCategorizeResult {
     pub frontend_requests: Vec<ToolRequest>,
     pub remaining_requests: Vec<ToolRequest>,
+    /// All tool requests in the order the model issued them, across both groups
+    pub ordered_requests: Vec<ToolRequest>,
     pub filtered_response: Message,
     pub readonly_tools: HashSet<String>,
     pub regular_tools: HashSet<String>,
 }
 
+/// The tool calls the agent would make for a conversation, without running any of them
+#[derive(Debug, Clone)]
+pub struct ToolCallPreview {
+    pub frontend_requests: Vec<ToolRequest>,
+    pub other_requests: Vec<ToolRequest>,
+    /// The provider's response with frontend tool requests removed
+    pub filtered_message: Message,
+}
+
 /// The main goose Agent
 pub struct Agent {
     pub(super) provider: Mutex<Option<Arc<dyn Provider>>>,
@@ -104,6 +121,7 @@ pub struct Agent {
     pub(super) scheduler_service: Mutex<Option<Arc<dyn SchedulerTrait>>>,
     pub(super) retry_manager: RetryManager,
     pub(super) autopilot: Mutex<AutoPilot>,
+    pub(super) session_store: Mutex<SessionStore>,
 }
 
 #[derive(Clone, Debug)]
@@ -181,6 +199,7 @@ impl Agent {
             scheduler_service: Mutex::new(None),
             retry_manager,
             autopilot: Mutex::new(AutoPilot::new()),
+            session_store: Mutex::new(SessionStore::new()),
         }
     }
 
@@ -266,18 +285,45 @@ impl Agent {
         let (readonly_tools, regular_tools) = Self::categorize_tools_by_annotation(tools);
 
         // Categorize tool requests
-        let (frontend_requests, remaining_requests, filtered_response) =
+        let (frontend_requests, remaining_requests, ordered_requests, filtered_response) =
             self.categorize_tool_requests(response).await;
 
         ToolCategorizeResult {
             frontend_requests,
             remaining_requests,
+            ordered_requests,
             filtered_response,
             readonly_tools,
             regular_tools,
         }
     }
 
+    /// Run a single provider turn over `messages` and report the tool calls it asks for.
+    ///
+    /// Nothing is executed, recorded or added to the conversation; this reuses the same
+    /// categorization as `reply` so prompts and approval flows can be checked ahead of time.
+    pub async fn preview_tool_calls(&self, messages: &[Message]) -> Result<ToolCallPreview> {
+        let (tools, toolshim_tools, system_prompt) = self.prepare_tools_and_prompt().await?;
+        let (response, _usage) = Self::generate_response_from_provider(
+            self.provider().await?,
+            &system_prompt,
+            messages,
+            Self::max_history_messages(None),
+            &tools,
+            &toolshim_tools,
+        )
+        .await?;
+
+        let (frontend_requests, other_requests, _, filtered_message) =
+            self.categorize_tool_requests(&response).await;
+
+        Ok(ToolCallPreview {
+            frontend_requests,
+            other_requests,
+            filtered_message,
+        })
+    }
+
     async fn handle_approved_and_denied_tools(
         &self,
         permission_check_result: &PermissionCheckResult,
@@ -334,6 +380,12 @@ impl Agent {
         *scheduler_service = Some(scheduler);
     }
 
+    /// Store the agent records session data through, such as subagent transcripts
+    pub async fn set_session_store(&self, session_store: SessionStore) {
+        let mut store = self.session_store.lock().await;
+        *store = session_store;
+    }
+
     pub async fn disable_router_for_recipe(&self) {
         self.tool_route_manager.disable_router_for_recipe().await;
     }
@@ -455,6 +507,7 @@ impl Agent {
                 task_config,
                 &self.tasks_manager,
                 cancellation_token,
+                TranscriptStore::new(self.session_store.lock().await.clone(), session.as_ref()),
             )
             .await
         } else if tool_call.name == DYNAMIC_TASK_TOOL_NAME_PREFIX {
@@ -547,38 +600,27 @@ impl Agent {
             } else if let Some(session_config) = session {
                 // Update session metadata with new TODO content
                 match session::storage::get_path(session_config.id.clone()) {
-                    Ok(path) => match session::storage::read_metadata(&path) {
-                        Ok(mut metadata) => {
-                            let todo_state = session::TodoState::new(content);
+                    Ok(path) => {
+                        let todo_state = session::TodoState::new(content);
+                        let update_result = session::storage::modify_metadata(&path, |metadata| {
                             todo_state
                                 .to_extension_data(&mut metadata.extension_data)
                                 .ok();
-
-                            let path_clone = path.clone();
-                            let metadata_clone = metadata.clone();
-                            let update_result = tokio::task::spawn(async move {
-                                session::storage::update_metadata(&path_clone, &metadata_clone)
-                                    .await
-                            })
-                            .await;
-
-                            match update_result {
-                                Ok(Ok(_)) => ToolCallResult::from(Ok(vec![Content::text(
-                                    format!("Updated ({} chars)", char_count),
-                                )])),
-                                _ => ToolCallResult::from(Err(ErrorData::new(
-                                    ErrorCode::INTERNAL_ERROR,
-                                    "Failed to update session metadata".to_string(),
-                                    None,
-                                ))),
-                            }
+                        })
+                        .await;
+
+                        match update_result {
+                            Ok(_) => ToolCallResult::from(Ok(vec![Content::text(format!(
+                                "Updated ({} chars)",
+                                char_count
+                            ))])),
+                            Err(_) => ToolCallResult::from(Err(ErrorData::new(
+                                ErrorCode::INTERNAL_ERROR,
+                                "Failed to update session metadata".to_string(),
+                                None,
+                            ))),
                         }
-                        Err(_) => ToolCallResult::from(Err(ErrorData::new(
-                            ErrorCode::INTERNAL_ERROR,
-                            "Failed to read session metadata".to_string(),
-                            None,
-                        ))),
-                    },
+                    }
                     Err(_) => ToolCallResult::from(Err(ErrorData::new(
                         ErrorCode::INTERNAL_ERROR,
                         "Failed to get session path".to_string(),
@@ -1020,6 +1062,8 @@ impl Agent {
                 .unwrap_or_else(|| {
                     config.get_param("GOOSE_MAX_TURNS").unwrap_or(DEFAULT_MAX_TURNS)
                 });
+            let max_history_messages = Self::max_history_messages(session.as_ref());
+            let usage_thresholds = UsageThresholds::from_config(config);
 
             loop {
                 if is_token_cancelled(&cancel_token) {
@@ -1062,6 +1106,7 @@ impl Agent {
                     self.provider().await?,
                     &system_prompt,
                     messages.messages(),
+                    max_history_messages,
                     &tools,
                     &toolshim_tools,
                 ).await?;
@@ -1101,8 +1146,22 @@ impl Agent {
                             // Record usage for the session
                             if let Some(ref session_config) = &session {
                                 if let Some(ref usage) = usage {
-                                    Self::update_session_metrics(session_config, usage, messages.len())
+                                    let (before, after) = Self::update_session_metrics(session_config, usage, messages.len())
                                         .await?;
+
+                                    if !usage_thresholds.is_empty() {
+                                        let pricing = match (usage_thresholds.cost_usd, config.get_param::<String>("GOOSE_PROVIDER")) {
+                                            (Some(_), Ok(provider_name)) => get_model_pricing(&provider_name, &usage.model).await,
+                                            _ => None,
+                                        };
+                                        for warning in usage_thresholds.crossed(&before, &after, pricing.as_ref()) {
+                                            tracing::warn!("{}", warning);
+                                            yield AgentEvent::McpNotification((
+                                                USAGE_ALERT_NOTIFICATION_TYPE.to_string(),
+                                                usage_alert_notification(warning),
+                                            ));
+                                        }
+                                    }
                                 }
                             }
 
@@ -1110,13 +1169,13 @@ impl Agent {
                                 let ToolCategorizeResult {
                                     frontend_requests,
                                     remaining_requests,
+                                    ordered_requests,
                                     filtered_response,
                                     readonly_tools,
                                     regular_tools,
                                 } = self.categorize_tools(&response, &tools).await;
-                                let requests_to_record: Vec<ToolRequest> = frontend_requests.iter().chain(remaining_requests.iter()).cloned().collect();
                                 self.tool_route_manager
-                                    .record_tool_requests(&requests_to_record)
+                                    .record_tool_requests(&ordered_requests)
                                     .await;
 
                                 yield AgentEvent::Message(filtered_response.clone());
@@ -24,6 +24,7 @@ mod tool_execution;
 mod tool_route_manager;
 mod tool_router_index_manager;
 pub mod types;
+pub mod usage_alert;
 
 pub use agent::{Agent, AgentEvent};
 pub use extension::ExtensionConfig;
@@ -7,10 +7,15 @@ use crate::agents::router_tools::llm_search_tool_prompt;
 use crate::providers::base::get_current_model;
 use crate::{config::Config, prompt_template, utils::sanitize_unicode_tags};
 
+/// Marker appended to extension instructions that were cut to fit the budget
+const TRUNCATED_INSTRUCTIONS_MARKER: &str =
+    "\n[... instructions truncated to fit the system prompt budget]";
+
 pub struct PromptManager {
     system_prompt_override: Option<String>,
     system_prompt_extras: Vec<String>,
     current_date_timestamp: String,
+    extension_instructions_budget: Option<usize>,
 }
 
 impl Default for PromptManager {
@@ -26,9 +31,17 @@ impl PromptManager {
             system_prompt_extras: Vec::new(),
             // Use the fixed current date time so that prompt cache can be used.
             current_date_timestamp: Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            extension_instructions_budget: None,
         }
     }
 
+    /// Limit the combined size, in characters, of all extension instructions in the system prompt.
+    ///
+    /// When unset, the GOOSE_EXTENSION_INSTRUCTIONS_BUDGET config value is used if present.
+    pub fn set_extension_instructions_budget(&mut self, budget: Option<usize>) {
+        self.extension_instructions_budget = budget;
+    }
+
     /// Add an additional instruction to the system prompt
     pub fn add_system_prompt_extra(&mut self, instruction: String) {
         self.system_prompt_extras.push(instruction);
@@ -72,6 +85,7 @@ impl PromptManager {
     ) -> String {
         let mut context: HashMap<&str, Value> = HashMap::new();
         let mut extensions_info = extensions_info.clone();
+        let config = Config::global();
 
         // Add frontend instructions to extensions_info to simplify json rendering
         if let Some(frontend_instructions) = frontend_instructions {
@@ -82,7 +96,7 @@ impl PromptManager {
             ));
         }
 
-        let sanitized_extensions_info: Vec<ExtensionInfo> = extensions_info
+        let mut sanitized_extensions_info: Vec<ExtensionInfo> = extensions_info
             .into_iter()
             .map(|mut ext_info| {
                 ext_info.instructions = sanitize_unicode_tags(&ext_info.instructions);
@@ -90,6 +104,26 @@ impl PromptManager {
             })
             .collect();
 
+        let budget = self.extension_instructions_budget.or_else(|| {
+            config
+                .get_param::<usize>("GOOSE_EXTENSION_INSTRUCTIONS_BUDGET")
+                .ok()
+        });
+        if let Some(budget) = budget {
+            let trimmed = fit_extension_instructions(&mut sanitized_extensions_info, budget);
+            if !trimmed.is_empty() {
+                tracing::warn!(
+                    "Extension instructions exceeded the budget of {} characters, trimmed: {}",
+                    budget,
+                    trimmed
+                        .iter()
+                        .map(|(name, removed)| format!("{} (-{} chars)", name, removed))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+
         context.insert(
             "extensions",
             serde_json::to_value(sanitized_extensions_info).unwrap(),
@@ -139,7 +173,6 @@ impl PromptManager {
         };
 
         let mut system_prompt_extras = self.system_prompt_extras.clone();
-        let config = Config::global();
         let goose_mode = config.get_param("GOOSE_MODE").unwrap_or("auto".to_string());
         if goose_mode == "chat" {
             system_prompt_extras.push(
@@ -173,6 +206,70 @@ impl PromptManager {
     }
 }
 
+/// Trim extension instructions so their combined length fits within `budget` characters.
+///
+/// The budget is shared evenly: extensions needing less than an equal share keep their full
+/// instructions and leave the remainder to the others, so only the largest instructions are cut.
+/// Ties are broken by name so the outcome is deterministic. Returns the name and number of
+/// characters removed for each trimmed extension.
+fn fit_extension_instructions(
+    extensions: &mut [ExtensionInfo],
+    budget: usize,
+) -> Vec<(String, usize)> {
+    let total: usize = extensions
+        .iter()
+        .map(|ext| ext.instructions.chars().count())
+        .sum();
+    if total <= budget {
+        return Vec::new();
+    }
+
+    let mut order: Vec<usize> = (0..extensions.len()).collect();
+    order.sort_by_key(|&i| {
+        (
+            extensions[i].instructions.chars().count(),
+            extensions[i].name.clone(),
+        )
+    });
+
+    let mut remaining = budget;
+    let mut trimmed = Vec::new();
+    for (position, &index) in order.iter().enumerate() {
+        let share = remaining / (order.len() - position);
+        let ext = &mut extensions[index];
+        let length = ext.instructions.chars().count();
+
+        if length <= share {
+            remaining -= length;
+            continue;
+        }
+
+        // The marker counts against the share; when the share can't even hold it the
+        // instructions are dropped rather than pushing the total over the budget
+        let marker_length = TRUNCATED_INSTRUCTIONS_MARKER.chars().count();
+        let truncated = if share >= marker_length {
+            let mut truncated: String = ext
+                .instructions
+                .chars()
+                .take(share - marker_length)
+                .collect();
+            truncated.push_str(TRUNCATED_INSTRUCTIONS_MARKER);
+            truncated
+        } else {
+            String::new()
+        };
+        trimmed.push((
+            ext.name.clone(),
+            length - share.saturating_sub(marker_length),
+        ));
+        remaining -= truncated.chars().count();
+        ext.instructions = truncated;
+    }
+
+    trimmed.sort();
+    trimmed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,4 +414,74 @@ mod tests {
         assert!(result.contains("Extension help"));
         assert!(result.contains("hidden instructions"));
     }
+
+    #[test]
+    fn test_fit_extension_instructions_trims_largest_first() {
+        let mut extensions = vec![
+            ExtensionInfo::new("small", &"s".repeat(100), false),
+            ExtensionInfo::new("large", &"l".repeat(2000), false),
+            ExtensionInfo::new("medium", &"m".repeat(400), false),
+        ];
+
+        let trimmed = fit_extension_instructions(&mut extensions, 1000);
+
+        // Small instructions fit within their share and are untouched
+        assert_eq!(extensions[0].instructions.len(), 100);
+        assert_eq!(extensions[2].instructions.len(), 400);
+        // The largest gets what is left over
+        assert!(extensions[1]
+            .instructions
+            .ends_with(TRUNCATED_INSTRUCTIONS_MARKER));
+        assert_eq!(extensions[1].instructions.chars().count(), 500);
+        assert_eq!(trimmed.len(), 1);
+        assert_eq!(trimmed[0].0, "large");
+
+        let total: usize = extensions.iter().map(|e| e.instructions.len()).sum();
+        assert!(total <= 1000);
+    }
+
+    #[test]
+    fn test_fit_extension_instructions_never_exceeds_budget() {
+        for budget in [0, 10, 100, 150, 1000] {
+            let mut extensions = vec![
+                ExtensionInfo::new("a", &"a".repeat(900), false),
+                ExtensionInfo::new("b", &"b".repeat(700), false),
+                ExtensionInfo::new("c", &"c".repeat(800), false),
+            ];
+            fit_extension_instructions(&mut extensions, budget);
+            let total: usize = extensions
+                .iter()
+                .map(|e| e.instructions.chars().count())
+                .sum();
+            assert!(total <= budget, "{} > {}", total, budget);
+        }
+    }
+
+    #[test]
+    fn test_fit_extension_instructions_within_budget_is_noop() {
+        let mut extensions = vec![ExtensionInfo::new("a", "short", false)];
+        assert!(fit_extension_instructions(&mut extensions, 100).is_empty());
+        assert_eq!(extensions[0].instructions, "short");
+    }
+
+    #[test]
+    fn test_build_system_prompt_applies_budget() {
+        let mut manager = PromptManager::new();
+        manager.set_extension_instructions_budget(Some(200));
+
+        let result = manager.build_system_prompt(
+            vec![
+                ExtensionInfo::new("big", &"x".repeat(5000), false),
+                ExtensionInfo::new("tiny", "tiny instructions", false),
+            ],
+            None,
+            Value::String("".to_string()),
+            None,
+            false,
+        );
+
+        assert!(result.contains("tiny instructions"));
+        assert!(result.contains("instructions truncated to fit the system prompt budget"));
+        assert!(!result.contains(&"x".repeat(500)));
+    }
 }
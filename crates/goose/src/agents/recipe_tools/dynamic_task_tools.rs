@@ -21,7 +21,7 @@ pub const DYNAMIC_TASK_TOOL_NAME_PREFIX: &str = "dynamic_task__create_task";
 pub fn create_dynamic_task_tool() -> Tool {
     Tool::new(
         DYNAMIC_TASK_TOOL_NAME_PREFIX.to_string(),
-        "Create tasks with instructions or prompt. For simple tasks, only include the instructions field. Extensions control: omit field = use all current extensions; empty array [] = no extensions; array with names = only those extensions. Specify extensions as shortnames (the prefixes for your tools). Specify return_last_only as true and have your subagent summarize its work in its last message to conserve your own context. Optional: title, description, extensions, settings, retry, response schema, context, activities. Arrays for multiple tasks.".to_string(),
+        "Create tasks with instructions or prompt. For simple tasks, only include the instructions field. Extensions control: omit field = use all current extensions; empty array [] = no extensions; array with names = only those extensions. Specify extensions as shortnames (the prefixes for your tools). Specify return_last_only as true and have your subagent summarize its work in its last message to conserve your own context. Optional: title, description, extensions, settings, retry, response schema, context, activities, group, tags. Arrays for multiple tasks.".to_string(),
         object!({
             "type": "object",
             "properties": {
@@ -53,6 +53,15 @@ pub fn create_dynamic_task_tool() -> Tool {
                             "return_last_only": {
                                 "type": "boolean",
                                 "description": "If true, return only the last message from the subagent (default: false, returns full conversation)"
+                            },
+                            "group": {
+                                "type": "string",
+                                "description": "Optional label, such as a phase name, shared by related tasks so progress displays can group them"
+                            },
+                            "tags": {
+                                "type": "array",
+                                "items": {"type": "string"},
+                                "description": "Optional labels such as 'critical'; pass them as report_tags when executing to limit the completion summary to these tasks"
                             }
                         },
                         "anyOf": [
@@ -276,6 +285,21 @@ pub async fn create_dynamic_task(
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
 
+                let group = task_param
+                    .get("group")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+
+                let tags = task_param
+                    .get("tags")
+                    .and_then(|v| v.as_array())
+                    .map(|tags| {
+                        tags.iter()
+                            .filter_map(|tag| tag.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
                 let task = Task {
                     id: uuid::Uuid::new_v4().to_string(),
                     task_type: TaskType::InlineRecipe,
@@ -283,6 +307,8 @@ pub async fn create_dynamic_task(
                         "recipe": recipe_json,
                         "return_last_only": return_last_only
                     }),
+                    group,
+                    tags,
                 };
                 tasks.push(task);
             }
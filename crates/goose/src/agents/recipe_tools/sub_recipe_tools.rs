@@ -63,13 +63,17 @@ fn create_tasks_from_params(
                     "name": sub_recipe.name.clone(),
                     "command_parameters": task_command_param,
                     "recipe_path": sub_recipe.path.clone(),
-                    "sequential_when_repeated": sub_recipe.sequential_when_repeated
+                    "sequential_when_repeated": sub_recipe.sequential_when_repeated,
+                    "env": sub_recipe.env,
+                    "cwd": sub_recipe.cwd
                 }
             });
             Task {
                 id: uuid::Uuid::new_v4().to_string(),
                 task_type: TaskType::SubRecipe,
                 payload,
+                group: None,
+                tags: Vec::new(),
             }
         })
         .collect();
@@ -14,6 +14,8 @@ mod tests {
             values: Some(HashMap::from([("key1".to_string(), "value1".to_string())])),
             sequential_when_repeated: true,
             description: Some("Test subrecipe".to_string()),
+            env: None,
+            cwd: None,
         };
         sub_recipe
     }
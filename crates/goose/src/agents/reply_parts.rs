@@ -6,18 +6,72 @@ use async_stream::try_stream;
 use futures::stream::StreamExt;
 
 use super::super::agents::Agent;
+use super::types::SessionConfig;
+use super::usage_alert::AccumulatedUsage;
+use crate::config::Config;
+use crate::context_mgmt::window::window_messages;
 use crate::conversation::message::{Message, MessageContent, ToolRequest};
 use crate::conversation::Conversation;
+use crate::model::ModelConfig;
 use crate::providers::base::{stream_from_single_message, MessageStream, Provider, ProviderUsage};
 use crate::providers::errors::ProviderError;
+use crate::providers::response_cache::ResponseCache;
+use crate::providers::timeout::{first_chunk_within, with_request_timeout};
 use crate::providers::toolshim::{
-    augment_message_with_tool_calls, convert_tool_messages_to_text,
-    modify_system_prompt_for_tool_json, OllamaInterpreter,
+    augment_message_with_retries, convert_tool_messages_to_text,
+    modify_system_prompt_for_tool_json, toolshim_retry_config, OllamaInterpreter,
 };
 
 use crate::session;
 use rmcp::model::Tool;
 
+/// The messages to send the provider: only the most recent `max_history_messages` when a
+/// window is set, with tool messages converted to text when toolshim is enabled
+fn messages_for_provider(
+    config: &ModelConfig,
+    messages: &[Message],
+    max_history_messages: Option<usize>,
+) -> Conversation {
+    let messages = match max_history_messages {
+        Some(limit) => window_messages(messages, limit),
+        None => messages,
+    };
+    if config.toolshim {
+        convert_tool_messages_to_text(messages)
+    } else {
+        Conversation::new_unvalidated(messages.to_vec())
+    }
+}
+
+/// Positions in `content` of tool requests that repeat an earlier request in the same
+/// message with the same tool name and arguments
+fn duplicate_tool_request_positions(content: &[MessageContent]) -> HashSet<usize> {
+    let mut seen: Vec<(&str, &serde_json::Value)> = Vec::new();
+    let mut duplicates = HashSet::new();
+
+    for (position, item) in content.iter().enumerate() {
+        let MessageContent::ToolRequest(req) = item else {
+            continue;
+        };
+        let Ok(tool_call) = &req.tool_call else {
+            continue;
+        };
+        let key = (tool_call.name.as_str(), &tool_call.arguments);
+        if seen.contains(&key) {
+            tracing::debug!(
+                "Dropping duplicate call to {} (request {})",
+                tool_call.name,
+                req.id
+            );
+            duplicates.insert(position);
+        } else {
+            seen.push(key);
+        }
+    }
+
+    duplicates
+}
+
 async fn toolshim_postprocess(
     response: Message,
     toolshim_tools: &[Tool],
@@ -26,9 +80,13 @@ async fn toolshim_postprocess(
         ProviderError::ExecutionError(format!("Failed to create OllamaInterpreter: {}", e))
     })?;
 
-    augment_message_with_tool_calls(&interpreter, response, toolshim_tools)
-        .await
-        .map_err(|e| ProviderError::ExecutionError(format!("Failed to augment message: {}", e)))
+    Ok(augment_message_with_retries(
+        &interpreter,
+        response,
+        toolshim_tools,
+        &toolshim_retry_config(),
+    )
+    .await)
 }
 
 impl Agent {
@@ -106,38 +164,74 @@ impl Agent {
             })
     }
 
+    /// The history window for a session: its own max_history_messages, or
+    /// GOOSE_MAX_HISTORY_MESSAGES when it doesn't set one
+    pub(crate) fn max_history_messages(session: Option<&SessionConfig>) -> Option<usize> {
+        session
+            .and_then(|session| session.max_history_messages)
+            .or_else(|| {
+                Config::global()
+                    .get_param("GOOSE_MAX_HISTORY_MESSAGES")
+                    .ok()
+            })
+    }
+
     /// Generate a response from the LLM provider
     /// Handles toolshim transformations if needed
     pub(crate) async fn generate_response_from_provider(
         provider: Arc<dyn Provider>,
         system_prompt: &str,
         messages: &[Message],
+        max_history_messages: Option<usize>,
         tools: &[Tool],
         toolshim_tools: &[Tool],
     ) -> Result<(Message, ProviderUsage), ProviderError> {
         let config = provider.get_model_config();
+        let messages_for_provider = messages_for_provider(&config, messages, max_history_messages);
 
-        // Convert tool messages to text if toolshim is enabled
-        let messages_for_provider = if config.toolshim {
-            convert_tool_messages_to_text(messages)
-        } else {
-            Conversation::new_unvalidated(messages.to_vec())
-        };
-
-        // Call the provider to get a response
-        let (mut response, mut usage) = provider
-            .complete(system_prompt, messages_for_provider.messages(), tools)
-            .await?;
-
-        // Ensure we have token counts, estimating if necessary
-        usage
-            .ensure_tokens(
+        // Replay identical requests from the opt-in development cache
+        let cache = ResponseCache::from_env().map(|cache| {
+            let key = ResponseCache::key(
+                &config.model_name,
                 system_prompt,
                 messages_for_provider.messages(),
-                &response,
                 tools,
-            )
-            .await?;
+            );
+            (cache, key)
+        });
+        let cached = cache.as_ref().and_then(|(cache, key)| cache.get(key));
+
+        let (mut response, usage) = match cached {
+            Some(cached) => {
+                tracing::info!("Using cached provider response");
+                cached
+            }
+            None => {
+                // Call the provider to get a response
+                let (response, mut usage) = with_request_timeout(
+                    provider.request_timeout(),
+                    provider.complete(system_prompt, messages_for_provider.messages(), tools),
+                )
+                .await?;
+
+                // Ensure we have token counts, estimating if necessary
+                usage
+                    .ensure_tokens(
+                        system_prompt,
+                        messages_for_provider.messages(),
+                        &response,
+                        tools,
+                    )
+                    .await?;
+
+                if let Some((cache, key)) = &cache {
+                    if let Err(e) = cache.put(key, &response, &usage) {
+                        tracing::warn!("Failed to cache provider response: {}", e);
+                    }
+                }
+                (response, usage)
+            }
+        };
 
         crate::providers::base::set_current_model(&usage.model);
 
@@ -154,17 +248,12 @@ impl Agent {
         provider: Arc<dyn Provider>,
         system_prompt: &str,
         messages: &[Message],
+        max_history_messages: Option<usize>,
         tools: &[Tool],
         toolshim_tools: &[Tool],
     ) -> Result<MessageStream, ProviderError> {
         let config = provider.get_model_config();
-
-        // Convert tool messages to text if toolshim is enabled
-        let messages_for_provider = if config.toolshim {
-            convert_tool_messages_to_text(messages)
-        } else {
-            Conversation::new_unvalidated(messages.to_vec())
-        };
+        let messages_for_provider = messages_for_provider(&config, messages, max_history_messages);
 
         // Clone owned data to move into the async stream
         let system_prompt = system_prompt.to_owned();
@@ -172,22 +261,28 @@ impl Agent {
         let toolshim_tools = toolshim_tools.to_owned();
         let provider = provider.clone();
 
+        let timeout = provider.request_timeout();
         let mut stream = if provider.supports_streaming() {
-            provider
-                .stream(
+            let stream = with_request_timeout(
+                timeout,
+                provider.stream(
                     system_prompt.as_str(),
                     messages_for_provider.messages(),
                     &tools,
-                )
-                .await?
+                ),
+            )
+            .await?;
+            first_chunk_within(timeout, stream).await?
         } else {
-            let (message, mut usage) = provider
-                .complete(
+            let (message, mut usage) = with_request_timeout(
+                timeout,
+                provider.complete(
                     system_prompt.as_str(),
                     messages_for_provider.messages(),
                     &tools,
-                )
-                .await?;
+                ),
+            )
+            .await?;
 
             // Ensure we have token counts for non-streaming case
             usage
@@ -223,14 +318,32 @@ impl Agent {
     /// Returns:
     /// - frontend_requests: Tool requests that should be handled by the frontend
     /// - other_requests: All other tool requests (including requests to enable extensions)
+    /// - ordered_requests: Every tool request, interleaved as it appeared in the response
     /// - filtered_message: The original message with frontend tool requests removed
+    ///
+    /// Requests within each returned vec keep the order in which the model issued them.
     pub(crate) async fn categorize_tool_requests(
         &self,
         response: &Message,
-    ) -> (Vec<ToolRequest>, Vec<ToolRequest>, Message) {
-        // First collect all tool requests
-        let tool_requests: Vec<ToolRequest> = response
+    ) -> (
+        Vec<ToolRequest>,
+        Vec<ToolRequest>,
+        Vec<ToolRequest>,
+        Message,
+    ) {
+        // Some providers repeat the exact same call within one response; run it only once
+        // and drop the copies from the message so no request is left without a response
+        let duplicates = duplicate_tool_request_positions(&response.content);
+        let content: Vec<&MessageContent> = response
             .content
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| !duplicates.contains(position))
+            .map(|(_, content)| content)
+            .collect();
+
+        // First collect all tool requests
+        let tool_requests: Vec<ToolRequest> = content
             .iter()
             .filter_map(|content| {
                 if let MessageContent::ToolRequest(req) = content {
@@ -245,7 +358,7 @@ impl Agent {
         let mut filtered_content = Vec::new();
 
         // Process each content item one by one
-        for content in &response.content {
+        for content in content {
             let should_include = match content {
                 MessageContent::ToolRequest(req) => {
                     if let Ok(tool_call) = &req.tool_call {
@@ -273,60 +386,158 @@ impl Agent {
         let mut frontend_requests = Vec::new();
         let mut other_requests = Vec::new();
 
-        for request in tool_requests {
+        for request in &tool_requests {
             if let Ok(tool_call) = &request.tool_call {
                 if self.is_frontend_tool(&tool_call.name).await {
-                    frontend_requests.push(request);
+                    frontend_requests.push(request.clone());
                 } else {
-                    other_requests.push(request);
+                    other_requests.push(request.clone());
                 }
             } else {
                 // If there's an error in the tool call, add it to other_requests
-                other_requests.push(request);
+                other_requests.push(request.clone());
             }
         }
 
-        (frontend_requests, other_requests, filtered_message)
+        (
+            frontend_requests,
+            other_requests,
+            tool_requests,
+            filtered_message,
+        )
     }
 
+    /// Record usage for the session, returning the accumulated totals before and after
     pub(crate) async fn update_session_metrics(
         session_config: &crate::agents::types::SessionConfig,
         usage: &ProviderUsage,
         messages_length: usize,
-    ) -> Result<()> {
+    ) -> Result<(AccumulatedUsage, AccumulatedUsage)> {
         let session_file_path = match session::storage::get_path(session_config.id.clone()) {
             Ok(path) => path,
             Err(e) => {
                 return Err(anyhow::anyhow!("Failed to get session file path: {}", e));
             }
         };
-        let mut metadata = session::storage::read_metadata(&session_file_path)?;
+        // Hold the session's metadata lock so a concurrent rename isn't lost
+        let mut before = AccumulatedUsage::default();
+        let metadata = session::storage::modify_metadata(&session_file_path, |metadata| {
+            before = AccumulatedUsage::from_metadata(metadata);
 
-        metadata.schedule_id = session_config.schedule_id.clone();
+            metadata.schedule_id = session_config.schedule_id.clone();
+            metadata.last_activity = Some(chrono::Utc::now().timestamp());
 
-        metadata.total_tokens = usage.usage.total_tokens;
-        metadata.input_tokens = usage.usage.input_tokens;
-        metadata.output_tokens = usage.usage.output_tokens;
+            metadata.total_tokens = usage.usage.total_tokens;
+            metadata.input_tokens = usage.usage.input_tokens;
+            metadata.output_tokens = usage.usage.output_tokens;
 
-        metadata.message_count = messages_length + 1;
+            metadata.message_count = messages_length + 1;
 
-        let accumulate = |a: Option<i32>, b: Option<i32>| -> Option<i32> {
-            match (a, b) {
-                (Some(x), Some(y)) => Some(x + y),
-                _ => a.or(b),
-            }
-        };
-        metadata.accumulated_total_tokens =
-            accumulate(metadata.accumulated_total_tokens, usage.usage.total_tokens);
-        metadata.accumulated_input_tokens =
-            accumulate(metadata.accumulated_input_tokens, usage.usage.input_tokens);
-        metadata.accumulated_output_tokens = accumulate(
-            metadata.accumulated_output_tokens,
-            usage.usage.output_tokens,
-        );
+            let accumulate = |a: Option<i32>, b: Option<i32>| -> Option<i32> {
+                match (a, b) {
+                    (Some(x), Some(y)) => Some(x + y),
+                    _ => a.or(b),
+                }
+            };
+            metadata.accumulated_total_tokens =
+                accumulate(metadata.accumulated_total_tokens, usage.usage.total_tokens);
+            metadata.accumulated_input_tokens =
+                accumulate(metadata.accumulated_input_tokens, usage.usage.input_tokens);
+            metadata.accumulated_output_tokens = accumulate(
+                metadata.accumulated_output_tokens,
+                usage.usage.output_tokens,
+            );
+        })
+        .await?;
+
+        Ok((before, AccumulatedUsage::from_metadata(&metadata)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::extension::ExtensionConfig;
+    use mcp_core::tool::ToolCall;
+    use serde_json::json;
+
+    #[test]
+    fn test_messages_for_provider_applies_history_window() {
+        let messages: Vec<Message> = (0..6)
+            .map(|i| match i % 2 {
+                0 => Message::user().with_text(format!("question {}", i)),
+                _ => Message::assistant().with_text(format!("answer {}", i)),
+            })
+            .collect();
+        let config = ModelConfig::new_or_fail("test-model");
+
+        let windowed = messages_for_provider(&config, &messages, Some(2));
+        assert_eq!(windowed.len(), 2);
+        assert_eq!(windowed.first().unwrap().as_concat_text(), "question 4");
+        assert_eq!(messages_for_provider(&config, &messages, None).len(), 6);
 
-        session::storage::update_metadata(&session_file_path, &metadata).await?;
+        // The window also applies when tool messages are converted for toolshim
+        let config = config.with_toolshim(true);
+        assert_eq!(messages_for_provider(&config, &messages, Some(2)).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_categorize_tool_requests_drops_duplicates() {
+        let agent = Agent::new();
+        let read = || ToolCall::new("developer__shell", json!({"command": "ls"}));
+        let response = Message::assistant()
+            .with_text("Listing files")
+            .with_tool_request("first", Ok(read()))
+            .with_tool_request("second", Ok(read()))
+            .with_tool_request(
+                "third",
+                Ok(ToolCall::new("developer__shell", json!({"command": "pwd"}))),
+            );
+
+        let (frontend_requests, other_requests, _, filtered_message) =
+            agent.categorize_tool_requests(&response).await;
+
+        assert!(frontend_requests.is_empty());
+        let ids: Vec<&str> = other_requests.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["first", "third"]);
+        // The duplicate is removed from the message too, so it never needs a response
+        assert_eq!(filtered_message.content.len(), 3);
+        assert!(!filtered_message.get_tool_request_ids().contains("second"));
+    }
+
+    #[tokio::test]
+    async fn test_categorize_tool_requests_preserves_order() {
+        let agent = Agent::new();
+        agent
+            .add_extension(ExtensionConfig::Frontend {
+                name: "frontend".to_string(),
+                tools: vec![Tool::new(
+                    "show_dialog".to_string(),
+                    "Show a dialog".to_string(),
+                    serde_json::Map::new(),
+                )],
+                instructions: None,
+                bundled: None,
+                available_tools: vec![],
+            })
+            .await
+            .unwrap();
+
+        let call = |name: &str, n: i32| ToolCall::new(name, json!({"n": n}));
+        let response = Message::assistant()
+            .with_tool_request("a", Ok(call("developer__shell", 1)))
+            .with_tool_request("b", Ok(call("show_dialog", 2)))
+            .with_tool_request("c", Ok(call("developer__shell", 3)))
+            .with_tool_request("d", Ok(call("show_dialog", 4)));
 
-        Ok(())
+        let (frontend_requests, other_requests, ordered_requests, _) =
+            agent.categorize_tool_requests(&response).await;
+
+        let ids = |requests: &[ToolRequest]| -> Vec<String> {
+            requests.iter().map(|r| r.id.clone()).collect()
+        };
+        assert_eq!(ids(&frontend_requests), vec!["b", "d"]);
+        assert_eq!(ids(&other_requests), vec!["a", "c"]);
+        assert_eq!(ids(&ordered_requests), vec!["a", "b", "c", "d"]);
     }
 }
@@ -166,6 +166,7 @@ impl SubAgent {
                 Arc::clone(provider),
                 &system_prompt,
                 messages.messages(),
+                Agent::max_history_messages(None),
                 &tools,
                 &toolshim_tools,
             )
@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use etcetera::{choose_app_strategy, AppStrategy};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agents::subagent_execution_tool::notification_events::TaskInfo;
+use crate::config::{Config, APP_STRATEGY};
+
+/// Characters kept from the end of each task's output when a batch is saved
+const SAVED_OUTPUT_TAIL_CHARS: usize = 4000;
+
+/// The final state of every task in a batch, saved so it can be inspected after the
+/// completion notification has been sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResults {
+    pub batch_id: String,
+    pub completed_at: DateTime<Utc>,
+    pub tasks: Vec<TaskInfo>,
+}
+
+impl BatchResults {
+    pub fn new(batch_id: String, mut tasks: Vec<TaskInfo>) -> Self {
+        for task in &mut tasks {
+            task.current_output = output_tail(&task.current_output, SAVED_OUTPUT_TAIL_CHARS);
+        }
+        Self {
+            batch_id,
+            completed_at: Utc::now(),
+            tasks,
+        }
+    }
+}
+
+fn output_tail(output: &str, max_chars: usize) -> String {
+    let skip = output.chars().count().saturating_sub(max_chars);
+    output.chars().skip(skip).collect()
+}
+
+/// Whether completed batches are saved, set with GOOSE_PERSIST_TASK_RESULTS
+pub fn persist_batch_results_enabled() -> bool {
+    Config::global()
+        .get_param("GOOSE_PERSIST_TASK_RESULTS")
+        .unwrap_or(false)
+}
+
+/// Directory holding saved batches, under the goose data dir
+pub fn batch_results_dir() -> Result<PathBuf> {
+    Ok(choose_app_strategy(APP_STRATEGY.clone())?
+        .data_dir()
+        .join("task_batches"))
+}
+
+fn batch_results_path(dir: &Path, batch_id: &str) -> Result<PathBuf> {
+    // Batch ids become file names, so keep them to a safe character set
+    if batch_id.is_empty()
+        || !batch_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow!("Invalid batch id '{}'", batch_id));
+    }
+    Ok(dir.join(format!("{}.json", batch_id)))
+}
+
+pub fn save_batch_results(dir: &Path, results: &BatchResults) -> Result<PathBuf> {
+    let path = batch_results_path(dir, &results.batch_id)?;
+    fs::create_dir_all(dir)?;
+    fs::write(&path, serde_json::to_string_pretty(results)?)?;
+    Ok(path)
+}
+
+pub fn load_batch_results(dir: &Path, batch_id: &str) -> Result<BatchResults> {
+    let path = batch_results_path(dir, batch_id)?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow!("No saved results for batch '{}': {}", batch_id, e))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::subagent_execution_tool::task_types::TaskStatus;
+    use serde_json::json;
+
+    fn task_info(id: &str, status: TaskStatus, output: &str) -> TaskInfo {
+        TaskInfo {
+            id: id.to_string(),
+            status,
+            duration_secs: Some(2.0),
+            current_output: output.to_string(),
+            task_type: "inline_recipe".to_string(),
+            task_name: id.to_string(),
+            task_metadata: String::new(),
+            error: None,
+            result_data: Some(json!({"result": "done"})),
+            group: None,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_batch_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let long_output = format!("{}tail", "x".repeat(SAVED_OUTPUT_TAIL_CHARS));
+        let results = BatchResults::new(
+            "batch-1".to_string(),
+            vec![
+                task_info("task-1", TaskStatus::Completed, &long_output),
+                task_info("task-2", TaskStatus::Failed, "short"),
+            ],
+        );
+
+        let path = save_batch_results(dir.path(), &results).unwrap();
+        assert_eq!(path, dir.path().join("batch-1.json"));
+
+        let loaded = load_batch_results(dir.path(), "batch-1").unwrap();
+        assert_eq!(loaded.batch_id, "batch-1");
+        assert_eq!(loaded.tasks.len(), 2);
+        assert_eq!(
+            loaded.tasks[0].current_output.chars().count(),
+            SAVED_OUTPUT_TAIL_CHARS
+        );
+        assert!(loaded.tasks[0].current_output.ends_with("tail"));
+        assert_eq!(loaded.tasks[1].current_output, "short");
+        assert_eq!(loaded.tasks[1].result_data, Some(json!({"result": "done"})));
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_or_unsafe_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_batch_results(dir.path(), "missing").is_err());
+        assert!(load_batch_results(dir.path(), "../escape").is_err());
+    }
+}
@@ -2,7 +2,7 @@ use crate::agents::subagent_execution_tool::lib::{
     ExecutionResponse, ExecutionStats, SharedState, Task, TaskResult, TaskStatus,
 };
 use crate::agents::subagent_execution_tool::task_execution_tracker::{
-    DisplayMode, TaskExecutionTracker,
+    delta_updates_enabled, DisplayMode, TaskExecutionTracker,
 };
 use crate::agents::subagent_execution_tool::tasks::process_task;
 use crate::agents::subagent_execution_tool::workers::spawn_worker;
@@ -59,13 +59,18 @@ pub async fn execute_tasks_in_parallel(
     notifier: Sender<ServerNotification>,
     task_config: TaskConfig,
     cancellation_token: Option<CancellationToken>,
+    report_tags: Vec<String>,
 ) -> ExecutionResponse {
-    let task_execution_tracker = Arc::new(TaskExecutionTracker::new(
-        tasks.clone(),
-        DisplayMode::MultipleTasksOutput,
-        notifier,
-        cancellation_token.clone(),
-    ));
+    let task_execution_tracker = Arc::new(
+        TaskExecutionTracker::new(
+            tasks.clone(),
+            DisplayMode::MultipleTasksOutput,
+            notifier,
+            cancellation_token.clone(),
+        )
+        .with_report_tags(report_tags)
+        .with_delta_updates(delta_updates_enabled()),
+    );
     let start_time = Instant::now();
     let task_count = tasks.len();
 
@@ -96,7 +101,14 @@ pub async fn execute_tasks_in_parallel(
         worker_handles.push(handle);
     }
 
-    let results = collect_results(&mut result_rx, task_execution_tracker.clone(), task_count).await;
+    let cancellation_token = shared_state.cancellation_token.clone();
+    let mut results = collect_results(
+        &mut result_rx,
+        task_execution_tracker.clone(),
+        task_count,
+        &cancellation_token,
+    )
+    .await;
 
     for handle in worker_handles {
         if let Err(e) = handle.await {
@@ -104,6 +116,17 @@ pub async fn execute_tasks_in_parallel(
         }
     }
 
+    if cancellation_token.is_cancelled() {
+        for task_id in task_execution_tracker.cancel_unfinished_tasks().await {
+            results.push(TaskResult {
+                task_id,
+                status: TaskStatus::Cancelled,
+                data: None,
+                error: Some("Task cancelled".to_string()),
+            });
+        }
+    }
+
     task_execution_tracker.send_tasks_complete().await;
 
     let execution_time = start_time.elapsed().as_millis();
@@ -125,11 +148,16 @@ fn calculate_stats(results: &[TaskResult], execution_time_ms: u128) -> Execution
         .iter()
         .filter(|r| matches!(r.status, TaskStatus::Failed))
         .count();
+    let cancelled = results
+        .iter()
+        .filter(|r| matches!(r.status, TaskStatus::Cancelled))
+        .count();
 
     ExecutionStats {
         total_tasks: results.len(),
         completed,
         failed,
+        cancelled,
         execution_time_ms,
     }
 }
@@ -183,17 +211,23 @@ fn create_empty_response() -> ExecutionResponse {
             total_tasks: 0,
             completed: 0,
             failed: 0,
+            cancelled: 0,
             execution_time_ms: 0,
         },
     }
 }
+// Gather results until every task has reported or the run is cancelled
 async fn collect_results(
     result_rx: &mut mpsc::Receiver<TaskResult>,
     task_execution_tracker: Arc<TaskExecutionTracker>,
     expected_count: usize,
+    cancellation_token: &CancellationToken,
 ) -> Vec<TaskResult> {
     let mut results = Vec::new();
-    while let Some(result) = result_rx.recv().await {
+    while let Some(result) = tokio::select! {
+        result = result_rx.recv() => result,
+        _ = cancellation_token.cancelled() => None,
+    } {
         task_execution_tracker
             .complete_task(&result.task_id, result.clone())
             .await;
@@ -215,6 +249,7 @@ fn create_error_response(error: String) -> ExecutionResponse {
             total_tasks: 0,
             completed: 0,
             failed: 1,
+            cancelled: 0,
             execution_time_ms: 0,
         },
     }
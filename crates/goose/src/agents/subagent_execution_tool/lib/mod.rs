@@ -28,6 +28,11 @@ pub async fn execute_tasks(
     .map_err(|e| format!("Failed to parse task_ids: {}", e))?;
 
     let tasks = tasks_manager.get_tasks(&task_ids).await?;
+    let report_tags: Vec<String> = match input.get("report_tags") {
+        Some(tags) => serde_json::from_value(tags.clone())
+            .map_err(|e| format!("Failed to parse report_tags: {}", e))?,
+        None => Vec::new(),
+    };
 
     let task_count = tasks.len();
     match execution_mode {
@@ -55,6 +60,7 @@ pub async fn execute_tasks(
                     notifier.clone(),
                     task_config,
                     cancellation_token,
+                    report_tags,
                 )
                 .await;
                 handle_response(response)
@@ -1,3 +1,4 @@
+pub mod batch_results;
 mod executor;
 pub mod lib;
 pub mod notification_events;
@@ -6,5 +7,6 @@ pub mod task_execution_tracker;
 pub mod task_types;
 pub mod tasks;
 pub mod tasks_manager;
+pub mod transcript;
 pub mod utils;
 pub mod workers;
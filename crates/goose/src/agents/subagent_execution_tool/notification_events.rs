@@ -1,4 +1,5 @@
 use crate::agents::subagent_execution_tool::task_types::TaskStatus;
+use crate::utils::safe_truncate;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -12,10 +13,27 @@ pub enum TaskExecutionNotificationEvent {
         stats: TaskExecutionStats,
         tasks: Vec<TaskInfo>,
     },
+    /// Every task in the batch, sent once before any `tasks_delta` when delta updates are on
+    #[serde(rename = "tasks_started")]
+    TasksStarted {
+        stats: TaskExecutionStats,
+        tasks: Vec<TaskInfo>,
+    },
+    /// Only the tasks whose status or output changed since the previous update
+    #[serde(rename = "tasks_delta")]
+    TasksDelta {
+        stats: TaskExecutionStats,
+        tasks: Vec<TaskInfo>,
+    },
     #[serde(rename = "tasks_complete")]
     TasksComplete {
         stats: TaskCompletionStats,
         failed_tasks: Vec<FailedTaskInfo>,
+        #[serde(default)]
+        failure_groups: Vec<FailureGroup>,
+        /// Id the final results were saved under, when batch results are persisted
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        batch_id: Option<String>,
     },
 }
 
@@ -26,6 +44,14 @@ pub struct TaskExecutionStats {
     pub running: usize,
     pub completed: usize,
     pub failed: usize,
+    #[serde(default)]
+    pub cancelled: usize,
+    /// Seconds since the first task started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed_secs: Option<f64>,
+    /// Estimated seconds until every task has finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_secs: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +59,8 @@ pub struct TaskCompletionStats {
     pub total: usize,
     pub completed: usize,
     pub failed: usize,
+    #[serde(default)]
+    pub cancelled: usize,
     pub success_rate: f64,
 }
 
@@ -47,6 +75,8 @@ pub struct TaskInfo {
     pub task_metadata: String,
     pub error: Option<String>,
     pub result_data: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +86,60 @@ pub struct FailedTaskInfo {
     pub error: Option<String>,
 }
 
+/// Failed tasks sharing the same normalized error
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FailureGroup {
+    pub signature: String,
+    pub count: usize,
+    pub task_ids: Vec<String>,
+}
+
+const MAX_ERROR_SIGNATURE_CHARS: usize = 120;
+
+/// The first line of an error with the parts that vary between tasks, such as ids,
+/// numbers and timestamps, collapsed so failures with the same cause compare equal.
+pub fn error_signature(error: Option<&str>) -> String {
+    let first_line = error
+        .and_then(|e| e.lines().map(str::trim).find(|line| !line.is_empty()))
+        .unwrap_or("unknown error");
+
+    let mut words: Vec<&str> = Vec::new();
+    for word in first_line.split_whitespace() {
+        let word = if word.chars().any(|c| c.is_ascii_digit()) {
+            "<n>"
+        } else {
+            word
+        };
+        if !(word == "<n>" && words.last() == Some(&"<n>")) {
+            words.push(word);
+        }
+    }
+
+    safe_truncate(&words.join(" "), MAX_ERROR_SIGNATURE_CHARS)
+}
+
+/// Group failed tasks by error signature, most common first
+pub fn group_failures(failed_tasks: &[FailedTaskInfo]) -> Vec<FailureGroup> {
+    let mut groups: Vec<FailureGroup> = Vec::new();
+    for task in failed_tasks {
+        let signature = error_signature(task.error.as_deref());
+        match groups.iter_mut().find(|group| group.signature == signature) {
+            Some(group) => {
+                group.count += 1;
+                group.task_ids.push(task.id.clone());
+            }
+            None => groups.push(FailureGroup {
+                signature,
+                count: 1,
+                task_ids: vec![task.id.clone()],
+            }),
+        }
+    }
+    // Stable sort keeps first-seen order between equally common errors
+    groups.sort_by(|a, b| b.count.cmp(&a.count));
+    groups
+}
+
 impl TaskExecutionNotificationEvent {
     pub fn line_output(task_id: String, output: String) -> Self {
         Self::LineOutput { task_id, output }
@@ -65,11 +149,30 @@ impl TaskExecutionNotificationEvent {
         Self::TasksUpdate { stats, tasks }
     }
 
+    pub fn tasks_started(stats: TaskExecutionStats, tasks: Vec<TaskInfo>) -> Self {
+        Self::TasksStarted { stats, tasks }
+    }
+
+    pub fn tasks_delta(stats: TaskExecutionStats, tasks: Vec<TaskInfo>) -> Self {
+        Self::TasksDelta { stats, tasks }
+    }
+
     pub fn tasks_complete(stats: TaskCompletionStats, failed_tasks: Vec<FailedTaskInfo>) -> Self {
+        let failure_groups = group_failures(&failed_tasks);
         Self::TasksComplete {
             stats,
             failed_tasks,
+            failure_groups,
+            batch_id: None,
+        }
+    }
+
+    /// Record the id a completed batch's results were saved under
+    pub fn with_saved_batch(mut self, saved_batch_id: String) -> Self {
+        if let Self::TasksComplete { batch_id, .. } = &mut self {
+            *batch_id = Some(saved_batch_id);
         }
+        self
     }
 
     /// Convert event to JSON format for MCP notification
@@ -95,6 +198,7 @@ impl TaskExecutionStats {
         running: usize,
         completed: usize,
         failed: usize,
+        cancelled: usize,
     ) -> Self {
         Self {
             total,
@@ -102,12 +206,21 @@ impl TaskExecutionStats {
             running,
             completed,
             failed,
+            cancelled,
+            elapsed_secs: None,
+            eta_secs: None,
         }
     }
+
+    pub fn with_timing(mut self, elapsed_secs: Option<f64>, eta_secs: Option<f64>) -> Self {
+        self.elapsed_secs = elapsed_secs;
+        self.eta_secs = eta_secs;
+        self
+    }
 }
 
 impl TaskCompletionStats {
-    pub fn new(total: usize, completed: usize, failed: usize) -> Self {
+    pub fn new(total: usize, completed: usize, failed: usize, cancelled: usize) -> Self {
         let success_rate = if total > 0 {
             (completed as f64 / total as f64) * 100.0
         } else {
@@ -118,6 +231,7 @@ impl TaskCompletionStats {
             total,
             completed,
             failed,
+            cancelled,
             success_rate,
         }
     }
@@ -143,7 +257,7 @@ mod tests {
 
     #[test]
     fn test_tasks_update_event_serialization() {
-        let stats = TaskExecutionStats::new(5, 2, 1, 1, 1);
+        let stats = TaskExecutionStats::new(5, 2, 1, 1, 1, 0);
         let tasks = vec![TaskInfo {
             id: "task-1".to_string(),
             status: TaskStatus::Running,
@@ -154,6 +268,7 @@ mod tests {
             task_metadata: "param=value".to_string(),
             error: None,
             result_data: None,
+            group: None,
         }];
 
         let event = TaskExecutionNotificationEvent::tasks_update(stats, tasks);
@@ -162,7 +277,14 @@ mod tests {
         assert_eq!(notification_data["type"], "task_execution");
         assert_eq!(notification_data["subtype"], "tasks_update");
         assert_eq!(notification_data["stats"]["total"], 5);
+        assert!(notification_data["stats"].get("eta_secs").is_none());
         assert_eq!(notification_data["tasks"].as_array().unwrap().len(), 1);
+
+        let stats = TaskExecutionStats::new(5, 2, 1, 1, 1, 0).with_timing(Some(12.5), Some(25.0));
+        let event = TaskExecutionNotificationEvent::tasks_update(stats, Vec::new());
+        let notification_data = event.to_notification_data();
+        assert_eq!(notification_data["stats"]["elapsed_secs"], 12.5);
+        assert_eq!(notification_data["stats"]["eta_secs"], 25.0);
     }
 
     #[test]
@@ -201,4 +323,44 @@ mod tests {
             _ => panic!("Event types don't match after roundtrip"),
         }
     }
+
+    #[test]
+    fn test_error_signature_collapses_variable_parts() {
+        assert_eq!(
+            error_signature(Some("Request 4f1c9a2e timed out after 30s\nat line 12")),
+            "Request <n> timed out after <n>"
+        );
+        assert_eq!(
+            error_signature(Some("Failed at 2024-05-01 12:00:03: auth error")),
+            "Failed at <n> auth error"
+        );
+        assert_eq!(error_signature(None), "unknown error");
+    }
+
+    #[test]
+    fn test_tasks_complete_groups_failures() {
+        let failed = |id: &str, error: &str| FailedTaskInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            error: Some(error.to_string()),
+        };
+        let failed_tasks = vec![
+            failed("task-1", "401 Unauthorized: invalid key"),
+            failed("task-2", "Timed out after 30s"),
+            failed("task-3", "Timed out after 45s"),
+            failed("task-4", "Timed out after 31s"),
+        ];
+
+        let event = TaskExecutionNotificationEvent::tasks_complete(
+            TaskCompletionStats::new(4, 0, 4, 0),
+            failed_tasks,
+        );
+        let data = event.to_notification_data();
+        let groups = data["failure_groups"].as_array().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0]["signature"], "Timed out after <n>");
+        assert_eq!(groups[0]["count"], 3);
+        assert_eq!(groups[1]["signature"], "<n> Unauthorized: invalid key");
+        assert_eq!(groups[1]["task_ids"], serde_json::json!(["task-1"]));
+    }
 }
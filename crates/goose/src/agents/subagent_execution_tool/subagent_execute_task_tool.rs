@@ -6,8 +6,10 @@ use serde_json::Value;
 use crate::agents::subagent_task_config::TaskConfig;
 use crate::agents::{
     subagent_execution_tool::lib::execute_tasks,
-    subagent_execution_tool::task_types::ExecutionMode,
-    subagent_execution_tool::tasks_manager::TasksManager, tool_execution::ToolCallResult,
+    subagent_execution_tool::task_types::{ExecutionMode, TaskResult},
+    subagent_execution_tool::tasks_manager::TasksManager,
+    subagent_execution_tool::transcript::{transcript_summary_from_config, TranscriptStore},
+    tool_execution::ToolCallResult,
 };
 use rmcp::object;
 use tokio::sync::mpsc;
@@ -48,6 +50,11 @@ pub fn create_subagent_execute_task_tool() -> Tool {
                         "type": "string",
                         "description": "Unique identifier for the task"
                     }
+                },
+                "report_tags": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Only report tasks with one of these tags in the completion summary. Every task still runs and shows in live progress."
                 }
             },
             "required": ["task_ids"]
@@ -66,6 +73,7 @@ pub async fn run_tasks(
     task_config: TaskConfig,
     tasks_manager: &TasksManager,
     cancellation_token: Option<CancellationToken>,
+    transcripts: TranscriptStore,
 ) -> ToolCallResult {
     let (notification_tx, notification_rx) = mpsc::channel::<ServerNotification>(100);
 
@@ -89,6 +97,17 @@ pub async fn run_tasks(
         {
             Ok(result) => {
                 let output = serde_json::to_string(&result).unwrap();
+
+                // Record what the subagents did in the parent session when configured
+                if let Some(mode) = transcript_summary_from_config() {
+                    let results: Vec<TaskResult> = result
+                        .get("results")
+                        .and_then(|results| serde_json::from_value(results.clone()).ok())
+                        .unwrap_or_default();
+                    if let Err(e) = transcripts.save(&results, mode).await {
+                        tracing::warn!("Failed to save subagent transcript: {}", e);
+                    }
+                }
                 Ok(vec![Content::text(output)])
             }
             Err(e) => Err(ErrorData {
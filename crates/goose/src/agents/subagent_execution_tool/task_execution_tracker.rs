@@ -8,12 +8,16 @@ use tokio::sync::{mpsc, RwLock};
 use tokio::time::{sleep, Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
+use crate::agents::subagent_execution_tool::batch_results::{
+    batch_results_dir, persist_batch_results_enabled, save_batch_results, BatchResults,
+};
 use crate::agents::subagent_execution_tool::notification_events::{
     FailedTaskInfo, TaskCompletionStats, TaskExecutionNotificationEvent, TaskExecutionStats,
     TaskInfo as EventTaskInfo,
 };
 use crate::agents::subagent_execution_tool::task_types::{Task, TaskInfo, TaskResult, TaskStatus};
-use crate::agents::subagent_execution_tool::utils::{count_by_status, get_task_name};
+use crate::agents::subagent_execution_tool::utils::{batch_timing, count_by_status, get_task_name};
+use crate::config::Config;
 use crate::utils::is_token_cancelled;
 use serde_json::Value;
 use tokio::sync::mpsc::Sender;
@@ -26,6 +30,51 @@ pub enum DisplayMode {
 
 const THROTTLE_INTERVAL_MS: u64 = 250;
 const COMPLETION_NOTIFICATION_DELAY_MS: u64 = 500;
+/// How long a must-deliver notification waits for room in a full channel
+const CRITICAL_NOTIFICATION_TIMEOUT_MS: u64 = 2000;
+
+/// How a notification is handed to the channel when it is full
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Delivery {
+    /// Drop the notification; used for high-frequency updates a later one supersedes
+    BestEffort,
+    /// Wait up to CRITICAL_NOTIFICATION_TIMEOUT_MS; used for status changes and completion,
+    /// which would otherwise leave the UI showing a stale state
+    Critical,
+}
+
+/// Whether multi-task runs send only the tasks that changed on each update, after an
+/// initial `tasks_started` snapshot. Enabled with GOOSE_SUBAGENT_DELTA_UPDATES.
+pub fn delta_updates_enabled() -> bool {
+    Config::global()
+        .get_param("GOOSE_SUBAGENT_DELTA_UPDATES")
+        .unwrap_or(false)
+}
+
+/// What the client was last sent of each task: its status and output length. Output only
+/// grows, so a longer output means the task printed new lines.
+type SentTaskStates = HashMap<String, (TaskStatus, usize)>;
+
+fn to_event_task_info(task_info: &TaskInfo, now: Instant) -> EventTaskInfo {
+    EventTaskInfo {
+        id: task_info.task.id.clone(),
+        status: task_info.status.clone(),
+        duration_secs: task_info.start_time.map(|start| {
+            if let Some(end) = task_info.end_time {
+                end.duration_since(start).as_secs_f64()
+            } else {
+                now.duration_since(start).as_secs_f64()
+            }
+        }),
+        current_output: task_info.current_output.clone(),
+        task_type: task_info.task.task_type.to_string(),
+        task_name: get_task_name(task_info).to_string(),
+        task_metadata: format_task_metadata(task_info),
+        error: task_info.error().cloned(),
+        result_data: task_info.data().cloned(),
+        group: task_info.task.group.clone(),
+    }
+}
 
 fn format_task_metadata(task_info: &TaskInfo) -> String {
     if let Some(params) = task_info.task.get_command_parameters() {
@@ -51,10 +100,17 @@ fn format_task_metadata(task_info: &TaskInfo) -> String {
 
 pub struct TaskExecutionTracker {
     tasks: Arc<RwLock<HashMap<String, TaskInfo>>>,
+    // Task ids in the order they were given, so notifications list tasks in a stable order
+    task_order: Vec<String>,
     last_refresh: Arc<RwLock<Instant>>,
     notifier: mpsc::Sender<ServerNotification>,
     display_mode: DisplayMode,
     cancellation_token: Option<CancellationToken>,
+    // Tags selecting the tasks reported on completion; empty reports every task
+    report_tags: Vec<String>,
+    delta_updates: bool,
+    // In delta mode, the task states the client has received; None until the first update
+    sent_states: Arc<RwLock<Option<SentTaskStates>>>,
 }
 
 impl TaskExecutionTracker {
@@ -64,6 +120,13 @@ impl TaskExecutionTracker {
         notifier: Sender<ServerNotification>,
         cancellation_token: Option<CancellationToken>,
     ) -> Self {
+        let mut task_order: Vec<String> = Vec::with_capacity(tasks.len());
+        for task in &tasks {
+            if !task_order.contains(&task.id) {
+                task_order.push(task.id.clone());
+            }
+        }
+
         let task_map = tasks
             .into_iter()
             .map(|task| {
@@ -84,10 +147,54 @@ impl TaskExecutionTracker {
 
         Self {
             tasks: Arc::new(RwLock::new(task_map)),
+            task_order,
             last_refresh: Arc::new(RwLock::new(Instant::now())),
             notifier,
             display_mode,
             cancellation_token,
+            report_tags: Vec::new(),
+            delta_updates: false,
+            sent_states: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Limit the completion summary to tasks with one of `tags`. Every task still runs and
+    /// appears in live updates; an empty list reports them all.
+    pub fn with_report_tags(mut self, tags: Vec<String>) -> Self {
+        self.report_tags = tags;
+        self
+    }
+
+    /// Send the full task list once, then only the tasks that changed, on each update
+    pub fn with_delta_updates(mut self, delta_updates: bool) -> Self {
+        self.delta_updates = delta_updates;
+        self
+    }
+
+    fn is_reported(&self, task_info: &TaskInfo) -> bool {
+        self.report_tags.is_empty() || task_info.task.has_any_tag(&self.report_tags)
+    }
+
+    fn ordered_tasks<'a>(
+        &'a self,
+        tasks: &'a HashMap<String, TaskInfo>,
+    ) -> impl Iterator<Item = &'a TaskInfo> {
+        self.task_order.iter().filter_map(|id| tasks.get(id))
+    }
+
+    // Save the batch under a fresh id, returning the id when it was written
+    fn save_results(&self, tasks: Vec<EventTaskInfo>) -> Option<String> {
+        let batch_id = uuid::Uuid::new_v4().to_string();
+        let results = BatchResults::new(batch_id.clone(), tasks);
+        match batch_results_dir().and_then(|dir| save_batch_results(&dir, &results)) {
+            Ok(path) => {
+                tracing::info!("Saved task batch results to {}", path.display());
+                Some(batch_id)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to save task batch results: {}", e);
+                None
+            }
         }
     }
 
@@ -95,28 +202,65 @@ impl TaskExecutionTracker {
         is_token_cancelled(&self.cancellation_token)
     }
 
-    fn log_notification_error<T>(&self, error: &mpsc::error::TrySendError<T>, context: &str) {
+    fn log_notification_error(&self, error: &impl std::fmt::Display, context: &str) {
         if !self.is_cancelled() {
             tracing::warn!("Failed to send {} notification: {}", context, error);
         }
     }
 
-    fn try_send_notification(&self, event: TaskExecutionNotificationEvent, context: &str) {
-        if let Err(e) = self
+    fn to_notification(event: TaskExecutionNotificationEvent) -> ServerNotification {
+        ServerNotification::LoggingMessageNotification(LoggingMessageNotification {
+            method: LoggingMessageNotificationMethod,
+            params: LoggingMessageNotificationParam {
+                data: event.to_notification_data(),
+                level: LoggingLevel::Info,
+                logger: None,
+            },
+            extensions: Default::default(),
+        })
+    }
+
+    // Each send returns whether the notification was queued
+    fn try_send_notification(&self, event: TaskExecutionNotificationEvent, context: &str) -> bool {
+        match self.notifier.try_send(Self::to_notification(event)) {
+            Ok(()) => true,
+            Err(e) => {
+                self.log_notification_error(&e, context);
+                false
+            }
+        }
+    }
+
+    async fn send_critical_notification(
+        &self,
+        event: TaskExecutionNotificationEvent,
+        context: &str,
+    ) -> bool {
+        match self
             .notifier
-            .try_send(ServerNotification::LoggingMessageNotification(
-                LoggingMessageNotification {
-                    method: LoggingMessageNotificationMethod,
-                    params: LoggingMessageNotificationParam {
-                        data: event.to_notification_data(),
-                        level: LoggingLevel::Info,
-                        logger: None,
-                    },
-                    extensions: Default::default(),
-                },
-            ))
+            .send_timeout(
+                Self::to_notification(event),
+                Duration::from_millis(CRITICAL_NOTIFICATION_TIMEOUT_MS),
+            )
+            .await
         {
-            self.log_notification_error(&e, context);
+            Ok(()) => true,
+            Err(e) => {
+                self.log_notification_error(&e, context);
+                false
+            }
+        }
+    }
+
+    async fn send_notification(
+        &self,
+        event: TaskExecutionNotificationEvent,
+        context: &str,
+        delivery: Delivery,
+    ) -> bool {
+        match delivery {
+            Delivery::BestEffort => self.try_send_notification(event, context),
+            Delivery::Critical => self.send_critical_notification(event, context).await,
         }
     }
 
@@ -206,50 +350,86 @@ impl TaskExecutionTracker {
         }
     }
 
-    async fn send_tasks_update(&self) {
+    async fn send_tasks_update(&self, delivery: Delivery) {
         if self.is_cancelled() {
             return;
         }
 
-        let tasks = self.tasks.read().await;
-        let task_list: Vec<_> = tasks.values().collect();
-        let (total, pending, running, completed, failed) = count_by_status(&tasks);
+        if self.delta_updates {
+            self.send_tasks_delta(delivery).await;
+            return;
+        }
 
-        let stats = TaskExecutionStats::new(total, pending, running, completed, failed);
+        let tasks = self.tasks.read().await;
+        let task_list: Vec<_> = self.ordered_tasks(&tasks).collect();
+        let stats = Self::execution_stats(&tasks);
 
+        let now = Instant::now();
         let event_tasks: Vec<EventTaskInfo> = task_list
             .iter()
+            .map(|task_info| to_event_task_info(task_info, now))
+            .collect();
+
+        let event = TaskExecutionNotificationEvent::tasks_update(stats, event_tasks);
+
+        self.send_notification(event, "tasks update", delivery)
+            .await;
+    }
+
+    fn execution_stats(tasks: &HashMap<String, TaskInfo>) -> TaskExecutionStats {
+        let (total, pending, running, completed, failed, cancelled) = count_by_status(tasks);
+        let (elapsed_secs, eta_secs) = batch_timing(tasks, Instant::now());
+        TaskExecutionStats::new(total, pending, running, completed, failed, cancelled)
+            .with_timing(elapsed_secs, eta_secs)
+    }
+
+    // Send every task the first time, then only those that changed since the last update
+    // the client received. A dropped update leaves the sent states alone, so its changes go
+    // out with the next one.
+    async fn send_tasks_delta(&self, delivery: Delivery) {
+        let mut sent_states = self.sent_states.write().await;
+        let tasks = self.tasks.read().await;
+        let stats = Self::execution_stats(&tasks);
+        let states: SentTaskStates = self
+            .ordered_tasks(&tasks)
             .map(|task_info| {
-                let now = Instant::now();
-                EventTaskInfo {
-                    id: task_info.task.id.clone(),
-                    status: task_info.status.clone(),
-                    duration_secs: task_info.start_time.map(|start| {
-                        if let Some(end) = task_info.end_time {
-                            end.duration_since(start).as_secs_f64()
-                        } else {
-                            now.duration_since(start).as_secs_f64()
-                        }
-                    }),
-                    current_output: task_info.current_output.clone(),
-                    task_type: task_info.task.task_type.to_string(),
-                    task_name: get_task_name(task_info).to_string(),
-                    task_metadata: format_task_metadata(task_info),
-                    error: task_info.error().cloned(),
-                    result_data: task_info.data().cloned(),
-                }
+                let state = (task_info.status.clone(), task_info.current_output.len());
+                (task_info.task.id.clone(), state)
             })
             .collect();
 
-        let event = TaskExecutionNotificationEvent::tasks_update(stats, event_tasks);
+        let now = Instant::now();
+        let event = match sent_states.as_ref() {
+            None => TaskExecutionNotificationEvent::tasks_started(
+                stats,
+                self.ordered_tasks(&tasks)
+                    .map(|task_info| to_event_task_info(task_info, now))
+                    .collect(),
+            ),
+            Some(sent) => TaskExecutionNotificationEvent::tasks_delta(
+                stats,
+                self.ordered_tasks(&tasks)
+                    .filter(|task_info| {
+                        sent.get(&task_info.task.id) != states.get(&task_info.task.id)
+                    })
+                    .map(|task_info| to_event_task_info(task_info, now))
+                    .collect(),
+            ),
+        };
+        drop(tasks);
 
-        self.try_send_notification(event, "tasks update");
+        if self
+            .send_notification(event, "tasks update", delivery)
+            .await
+        {
+            *sent_states = Some(states);
+        }
     }
 
     pub async fn refresh_display(&self) {
         match self.display_mode {
             DisplayMode::MultipleTasksOutput => {
-                self.send_tasks_update().await;
+                self.send_tasks_update(Delivery::BestEffort).await;
             }
             DisplayMode::SingleTaskOutput => {
                 // No dashboard display needed for single task output mode
@@ -258,7 +438,7 @@ impl TaskExecutionTracker {
         }
     }
 
-    // Force refresh without throttling - used for important status changes
+    // Force refresh without throttling or dropping - used for important status changes
     async fn force_refresh_display(&self) {
         match self.display_mode {
             DisplayMode::MultipleTasksOutput => {
@@ -267,7 +447,7 @@ impl TaskExecutionTracker {
                 *last_refresh = Instant::now() - Duration::from_millis(THROTTLE_INTERVAL_MS + 1);
                 drop(last_refresh);
 
-                self.send_tasks_update().await;
+                self.send_tasks_update(Delivery::Critical).await;
             }
             DisplayMode::SingleTaskOutput => {
                 // No dashboard display needed for single task output mode
@@ -275,18 +455,38 @@ impl TaskExecutionTracker {
         }
     }
 
-    pub async fn send_tasks_complete(&self) {
-        if self.is_cancelled() {
-            return;
+    /// Mark every task that has not finished as cancelled, returning their ids
+    pub async fn cancel_unfinished_tasks(&self) -> Vec<String> {
+        let mut tasks = self.tasks.write().await;
+        let now = Instant::now();
+        let mut cancelled = Vec::new();
+        for id in &self.task_order {
+            if let Some(task_info) = tasks.get_mut(id) {
+                if matches!(task_info.status, TaskStatus::Pending | TaskStatus::Running) {
+                    task_info.status = TaskStatus::Cancelled;
+                    if task_info.start_time.is_some() {
+                        task_info.end_time = Some(now);
+                    }
+                    cancelled.push(id.clone());
+                }
+            }
         }
+        cancelled
+    }
 
+    pub async fn send_tasks_complete(&self) {
         let tasks = self.tasks.read().await;
-        let (total, _, _, completed, failed) = count_by_status(&tasks);
+        let reported: HashMap<String, TaskInfo> = tasks
+            .iter()
+            .filter(|(_, task_info)| self.is_reported(task_info))
+            .map(|(id, task_info)| (id.clone(), task_info.clone()))
+            .collect();
+        let (total, _, _, completed, failed, cancelled) = count_by_status(&reported);
 
-        let stats = TaskCompletionStats::new(total, completed, failed);
+        let stats = TaskCompletionStats::new(total, completed, failed, cancelled);
 
-        let failed_tasks: Vec<FailedTaskInfo> = tasks
-            .values()
+        let failed_tasks: Vec<FailedTaskInfo> = self
+            .ordered_tasks(&reported)
             .filter(|task_info| matches!(task_info.status, TaskStatus::Failed))
             .map(|task_info| FailedTaskInfo {
                 id: task_info.task.id.clone(),
@@ -295,9 +495,233 @@ impl TaskExecutionTracker {
             })
             .collect();
 
-        let event = TaskExecutionNotificationEvent::tasks_complete(stats, failed_tasks);
-        self.try_send_notification(event, "tasks complete");
+        let mut event = TaskExecutionNotificationEvent::tasks_complete(stats, failed_tasks);
+        if persist_batch_results_enabled() {
+            let now = Instant::now();
+            let snapshot = self
+                .ordered_tasks(&tasks)
+                .map(|task_info| to_event_task_info(task_info, now))
+                .collect();
+            if let Some(batch_id) = self.save_results(snapshot) {
+                event = event.with_saved_batch(batch_id);
+            }
+        }
+        drop(tasks);
+
+        // After cancellation nobody may be draining the channel, so don't wait on it
+        if self.is_cancelled() {
+            self.try_send_notification(event, "tasks complete");
+            return;
+        }
+
+        self.send_critical_notification(event, "tasks complete")
+            .await;
         // Wait for the notification to be recieved and displayed before clearing the tasks
         sleep(Duration::from_millis(COMPLETION_NOTIFICATION_DELAY_MS)).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::subagent_execution_tool::task_types::TaskType;
+
+    #[tokio::test]
+    async fn test_tasks_update_preserves_task_order() {
+        let ids = ["task-c", "task-a", "task-b"];
+        let tasks = ids
+            .iter()
+            .map(|id| Task {
+                id: id.to_string(),
+                task_type: TaskType::InlineRecipe,
+                payload: Value::Null,
+                group: None,
+                tags: Vec::new(),
+            })
+            .collect();
+        let (notifier, mut receiver) = mpsc::channel(10);
+        let tracker =
+            TaskExecutionTracker::new(tasks, DisplayMode::MultipleTasksOutput, notifier, None);
+
+        tracker.start_task("task-a").await;
+
+        let Some(ServerNotification::LoggingMessageNotification(notification)) =
+            receiver.recv().await
+        else {
+            panic!("expected a tasks update notification");
+        };
+        let listed: Vec<_> = notification.params.data["tasks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| task["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(listed, ids);
+    }
+
+    #[tokio::test]
+    async fn test_delta_updates_send_only_changed_tasks() {
+        let tasks = ["task-a", "task-b", "task-c"]
+            .iter()
+            .map(|id| Task {
+                id: id.to_string(),
+                task_type: TaskType::InlineRecipe,
+                payload: Value::Null,
+                group: None,
+                tags: Vec::new(),
+            })
+            .collect();
+        let (notifier, mut receiver) = mpsc::channel(10);
+        let tracker =
+            TaskExecutionTracker::new(tasks, DisplayMode::MultipleTasksOutput, notifier, None)
+                .with_delta_updates(true);
+
+        let mut next_update = || {
+            let Ok(ServerNotification::LoggingMessageNotification(notification)) =
+                receiver.try_recv()
+            else {
+                panic!("expected a tasks notification");
+            };
+            let data = notification.params.data;
+            let ids: Vec<String> = data["tasks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|task| task["id"].as_str().unwrap().to_string())
+                .collect();
+            (data["subtype"].as_str().unwrap().to_string(), ids)
+        };
+
+        tracker.refresh_display().await;
+        assert_eq!(
+            next_update(),
+            (
+                "tasks_started".to_string(),
+                vec!["task-a".into(), "task-b".into(), "task-c".into()]
+            )
+        );
+
+        tracker.start_task("task-b").await;
+        assert_eq!(
+            next_update(),
+            ("tasks_delta".to_string(), vec!["task-b".to_string()])
+        );
+
+        tracker.refresh_display().await;
+        assert_eq!(next_update(), ("tasks_delta".to_string(), Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_unfinished_tasks() {
+        let tasks = ["task-a", "task-b", "task-c"]
+            .iter()
+            .map(|id| Task {
+                id: id.to_string(),
+                task_type: TaskType::InlineRecipe,
+                payload: Value::Null,
+                group: None,
+                tags: Vec::new(),
+            })
+            .collect();
+        let (notifier, _receiver) = mpsc::channel(10);
+        let tracker =
+            TaskExecutionTracker::new(tasks, DisplayMode::SingleTaskOutput, notifier, None);
+
+        tracker.start_task("task-a").await;
+        tracker
+            .complete_task(
+                "task-a",
+                TaskResult {
+                    task_id: "task-a".to_string(),
+                    status: TaskStatus::Completed,
+                    data: None,
+                    error: None,
+                },
+            )
+            .await;
+        tracker.start_task("task-b").await;
+
+        assert_eq!(
+            tracker.cancel_unfinished_tasks().await,
+            vec!["task-b".to_string(), "task-c".to_string()]
+        );
+        let tasks = tracker.tasks.read().await;
+        assert_eq!(count_by_status(&tasks), (3, 0, 0, 1, 0, 2));
+        assert!(tasks["task-b"].end_time.is_some());
+        assert!(tasks["task-c"].end_time.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tasks_complete_waits_for_full_channel() {
+        let (notifier, mut receiver) = mpsc::channel(1);
+        let tracker = Arc::new(TaskExecutionTracker::new(
+            Vec::new(),
+            DisplayMode::SingleTaskOutput,
+            notifier,
+            None,
+        ));
+
+        // Live output fills the channel, and further output is dropped
+        tracker.send_live_output("task", "first").await;
+        tracker.send_live_output("task", "second").await;
+
+        let completing = tokio::spawn({
+            let tracker = tracker.clone();
+            async move { tracker.send_tasks_complete().await }
+        });
+
+        let subtype = |notification: ServerNotification| match notification {
+            ServerNotification::LoggingMessageNotification(n) => n.params.data["subtype"].clone(),
+            _ => Value::Null,
+        };
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(subtype(receiver.recv().await.unwrap()), "line_output");
+        assert_eq!(subtype(receiver.recv().await.unwrap()), "tasks_complete");
+        completing.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tasks_complete_reports_tagged_tasks() {
+        let task = |id: &str, tags: &[&str]| Task {
+            id: id.to_string(),
+            task_type: TaskType::InlineRecipe,
+            payload: Value::Null,
+            group: None,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        let tasks = vec![task("must-pass", &["critical"]), task("best-effort", &[])];
+        let (notifier, mut receiver) = mpsc::channel(10);
+        let tracker =
+            TaskExecutionTracker::new(tasks, DisplayMode::SingleTaskOutput, notifier, None)
+                .with_report_tags(vec!["critical".to_string()]);
+
+        for (id, status) in [
+            ("must-pass", TaskStatus::Completed),
+            ("best-effort", TaskStatus::Failed),
+        ] {
+            tracker
+                .complete_task(
+                    id,
+                    TaskResult {
+                        task_id: id.to_string(),
+                        status,
+                        data: None,
+                        error: None,
+                    },
+                )
+                .await;
+        }
+        tracker.send_tasks_complete().await;
+
+        let Some(ServerNotification::LoggingMessageNotification(notification)) =
+            receiver.recv().await
+        else {
+            panic!("expected a tasks complete notification");
+        };
+        let data = notification.params.data;
+        assert_eq!(data["stats"]["total"], 1);
+        assert_eq!(data["stats"]["completed"], 1);
+        assert_eq!(data["stats"]["failed"], 0);
+        assert!(data["failed_tasks"].as_array().unwrap().is_empty());
+    }
+}
@@ -37,6 +37,12 @@ pub struct Task {
     pub id: String,
     pub task_type: TaskType,
     pub payload: Value,
+    /// Optional label shared by related tasks, e.g. a phase, so displays can cluster them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Optional labels, e.g. "critical", used to choose which tasks the completion summary reports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Task {
@@ -46,6 +52,11 @@ impl Task {
             .flatten()
     }
 
+    /// Whether the task has at least one of `tags`
+    pub fn has_any_tag(&self, tags: &[String]) -> bool {
+        self.tags.iter().any(|tag| tags.contains(tag))
+    }
+
     pub fn get_command_parameters(&self) -> Option<&Map<String, Value>> {
         self.get_sub_recipe()
             .and_then(|sr| sr.get("command_parameters"))
@@ -69,6 +80,18 @@ impl Task {
             .and_then(|sr| sr.get("recipe_path"))
             .and_then(|path| path.as_str())
     }
+
+    pub fn get_env(&self) -> Option<&Map<String, Value>> {
+        self.get_sub_recipe()
+            .and_then(|sr| sr.get("env"))
+            .and_then(|env| env.as_object())
+    }
+
+    pub fn get_cwd(&self) -> Option<&str> {
+        self.get_sub_recipe()
+            .and_then(|sr| sr.get("cwd"))
+            .and_then(|cwd| cwd.as_str())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,12 +104,14 @@ pub struct TaskResult {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TaskStatus {
     Pending,
     Running,
     Completed,
     Failed,
+    /// Stopped by cancellation before it could finish
+    Cancelled,
 }
 
 impl std::fmt::Display for TaskStatus {
@@ -96,6 +121,7 @@ impl std::fmt::Display for TaskStatus {
             TaskStatus::Running => write!(f, "Running"),
             TaskStatus::Completed => write!(f, "Completed"),
             TaskStatus::Failed => write!(f, "Failed"),
+            TaskStatus::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
@@ -143,6 +169,7 @@ pub struct ExecutionStats {
     pub total_tasks: usize,
     pub completed: usize,
     pub failed: usize,
+    pub cancelled: usize,
     pub execution_time_ms: u128,
 }
 
@@ -1,4 +1,5 @@
 use serde_json::Value;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, BufReader};
@@ -20,7 +21,7 @@ pub async fn process_task(
         task.clone(),
         task_execution_tracker,
         task_config,
-        cancellation_token,
+        cancellation_token.clone(),
     )
     .await
     {
@@ -32,7 +33,11 @@ pub async fn process_task(
         },
         Err(error) => TaskResult {
             task_id: task.id.clone(),
-            status: TaskStatus::Failed,
+            status: if cancellation_token.is_cancelled() {
+                TaskStatus::Cancelled
+            } else {
+                TaskStatus::Failed
+            },
             data: None,
             error: Some(error),
         },
@@ -139,6 +144,16 @@ fn build_command(task: &Task) -> Result<(Command, String), String> {
         .arg(path)
         .arg("--no-session");
 
+    if let Some(cwd) = task.get_cwd() {
+        command.current_dir(validate_task_cwd(cwd)?);
+    }
+
+    if let Some(env) = task.get_env() {
+        for (key, value) in env {
+            command.env(key, value.as_str().unwrap_or(&value.to_string()));
+        }
+    }
+
     for (key, value) in command_parameters {
         let key_str = key.to_string();
         let value_str = value.as_str().unwrap_or(&value.to_string()).to_string();
@@ -153,6 +168,20 @@ fn build_command(task: &Task) -> Result<(Command, String), String> {
     Ok((command, format!("sub-recipe {}", sub_recipe_name)))
 }
 
+// A task's working directory must be an absolute path to a directory we can read
+fn validate_task_cwd(cwd: &str) -> Result<PathBuf, String> {
+    let path = Path::new(cwd);
+    if !path.is_absolute() {
+        return Err(format!(
+            "Task working directory '{}' must be an absolute path",
+            cwd
+        ));
+    }
+    std::fs::read_dir(path)
+        .map_err(|e| format!("Task working directory '{}' is not accessible: {}", cwd, e))?;
+    Ok(path.to_path_buf())
+}
+
 async fn run_command(
     mut command: Command,
     output_identifier: &str,
@@ -261,3 +290,58 @@ fn process_output(stdout_output: String) -> Result<Value, String> {
         Ok(Value::String(stdout_output))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sub_recipe_task(env: Value, cwd: Value) -> Task {
+        Task {
+            id: "task-1".to_string(),
+            task_type: TaskType::SubRecipe,
+            payload: json!({
+                "sub_recipe": {
+                    "name": "build",
+                    "recipe_path": "/recipes/build.yaml",
+                    "command_parameters": {},
+                    "env": env,
+                    "cwd": cwd
+                }
+            }),
+            group: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_command_applies_env_and_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().to_string_lossy().to_string();
+        let task = sub_recipe_task(json!({"TARGET": "release"}), json!(cwd));
+
+        let (command, _) = build_command(&task).unwrap();
+        let command = command.as_std();
+        assert_eq!(command.get_current_dir(), Some(dir.path()));
+        assert!(command
+            .get_envs()
+            .any(|(key, value)| key == "TARGET" && value == Some("release".as_ref())));
+
+        let (command, _) = build_command(&sub_recipe_task(Value::Null, Value::Null)).unwrap();
+        assert_eq!(command.as_std().get_current_dir(), None);
+    }
+
+    #[test]
+    fn test_build_command_rejects_invalid_cwd() {
+        let relative = sub_recipe_task(Value::Null, json!("relative/dir"));
+        assert!(build_command(&relative)
+            .unwrap_err()
+            .contains("must be an absolute path"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(build_command(&sub_recipe_task(Value::Null, json!(missing)))
+            .unwrap_err()
+            .contains("is not accessible"));
+    }
+}
@@ -70,6 +70,8 @@ mod tests {
                     "recipe_path": "/test/path"
                 }
             }),
+            group: None,
+            tags: Vec::new(),
         }
     }
 
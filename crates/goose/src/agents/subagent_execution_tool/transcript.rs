@@ -0,0 +1,230 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::agents::subagent_execution_tool::task_types::{TaskResult, TaskStatus};
+use crate::agents::types::SessionConfig;
+use crate::config::Config;
+use crate::conversation::message::Message;
+use crate::session::{Identifier, SessionStore};
+use crate::utils::safe_truncate;
+
+/// Characters of each task's output kept in its transcript summary
+const SUMMARY_OUTPUT_CHARS: usize = 500;
+
+/// How subagent results are summarized in their transcript, set with
+/// GOOSE_SUBAGENT_TRANSCRIPT ("per_task" or "combined"). The summaries are appended to
+/// the parent session, so resuming it keeps what the subagents did.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TranscriptSummary {
+    /// One summary per task
+    PerTask,
+    /// A single summary covering every task in the batch
+    Combined,
+}
+
+pub fn transcript_summary_from_config() -> Option<TranscriptSummary> {
+    let value: String = Config::global()
+        .get_param("GOOSE_SUBAGENT_TRANSCRIPT")
+        .ok()?;
+    match value.trim().to_lowercase().as_str() {
+        "per_task" => Some(TranscriptSummary::PerTask),
+        "combined" => Some(TranscriptSummary::Combined),
+        _ => None,
+    }
+}
+
+fn task_output(result: &TaskResult) -> Option<String> {
+    let output = match result.data.as_ref()? {
+        Value::String(text) => text.clone(),
+        data => match data.get("result").or_else(|| data.get("partial_output")) {
+            Some(Value::String(text)) => text.clone(),
+            _ => data.to_string(),
+        },
+    };
+    let output = output.trim();
+    (!output.is_empty()).then(|| safe_truncate(output, SUMMARY_OUTPUT_CHARS))
+}
+
+fn task_summary(result: &TaskResult) -> String {
+    let mut summary = format!("Subagent task {} {}", result.task_id, result.status);
+    if let Some(error) = result
+        .error
+        .as_deref()
+        .filter(|_| !matches!(result.status, TaskStatus::Completed))
+    {
+        summary.push_str(&format!(": {}", error));
+    }
+    if let Some(output) = task_output(result) {
+        summary.push_str(&format!("\n{}", output));
+    }
+    summary
+}
+
+/// Summaries of `results` to record in the parent transcript, one entry per message
+pub fn summarize_results(results: &[TaskResult], mode: TranscriptSummary) -> Vec<String> {
+    if results.is_empty() {
+        return Vec::new();
+    }
+    let summaries = results.iter().map(task_summary);
+    match mode {
+        TranscriptSummary::PerTask => summaries.collect(),
+        TranscriptSummary::Combined => {
+            let completed = results
+                .iter()
+                .filter(|result| matches!(result.status, TaskStatus::Completed))
+                .count();
+            let header = format!(
+                "Subagent results: {}/{} tasks completed",
+                completed,
+                results.len()
+            );
+            vec![std::iter::once(header)
+                .chain(summaries)
+                .collect::<Vec<_>>()
+                .join("\n\n")]
+        }
+    }
+}
+
+/// Appends subagent transcripts to the parent session through a `SessionStore`
+#[derive(Clone)]
+pub struct TranscriptStore {
+    store: SessionStore,
+    parent: Option<String>,
+}
+
+impl TranscriptStore {
+    /// Transcripts for tasks run from `session`, appended to it in `store`
+    pub fn new(store: SessionStore, session: Option<&SessionConfig>) -> Self {
+        let parent = session.and_then(|session| match &session.id {
+            Identifier::Name(name) => Some(name.clone()),
+            Identifier::Path(path) => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned()),
+        });
+        Self { store, parent }
+    }
+
+    /// Append the summaries of `results` to the parent session, returning false when
+    /// there is no parent session or nothing to append
+    pub async fn save(&self, results: &[TaskResult], mode: TranscriptSummary) -> Result<bool> {
+        let Some(parent) = &self.parent else {
+            return Ok(false);
+        };
+        let summaries = summarize_results(results, mode);
+        if summaries.is_empty() {
+            return Ok(false);
+        }
+
+        let messages = summaries
+            .into_iter()
+            .map(|summary| Message::assistant().with_text(summary))
+            .collect();
+        self.store.append_messages(parent, messages).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversation::Conversation;
+    use crate::session::backend::SessionBackend;
+    use crate::session::info::{ModifiedRange, SortOrder};
+    use crate::session::{MemorySessionBackend, SessionMetadata};
+    use serde_json::json;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn results() -> Vec<TaskResult> {
+        vec![
+            TaskResult {
+                task_id: "task-1".to_string(),
+                status: TaskStatus::Completed,
+                data: Some(json!({"result": "Found 3 failing tests"})),
+                error: None,
+            },
+            TaskResult {
+                task_id: "task-2".to_string(),
+                status: TaskStatus::Failed,
+                data: None,
+                error: Some("recipe not found".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_per_task_summaries() {
+        let summaries = summarize_results(&results(), TranscriptSummary::PerTask);
+        assert_eq!(
+            summaries,
+            vec![
+                "Subagent task task-1 Completed\nFound 3 failing tests",
+                "Subagent task task-2 Failed: recipe not found",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_combined_summary() {
+        let summaries = summarize_results(&results(), TranscriptSummary::Combined);
+        assert_eq!(summaries.len(), 1);
+        assert!(summaries[0].starts_with("Subagent results: 1/2 tasks completed"));
+        assert!(summaries[0].contains("Found 3 failing tests"));
+        assert!(summaries[0].contains("recipe not found"));
+
+        assert!(summarize_results(&[], TranscriptSummary::Combined).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transcript_appended_to_parent_session() {
+        let backend = Arc::new(MemorySessionBackend::new());
+        let store = SessionStore::with_backend(backend.clone());
+        let metadata = SessionMetadata {
+            description: "Parent".to_string(),
+            ..SessionMetadata::default()
+        };
+        let conversation = Conversation::new_unvalidated(vec![Message::user().with_text("hi")]);
+        backend.write("parent", &metadata, &conversation).unwrap();
+
+        let session = SessionConfig {
+            id: Identifier::Name("parent".to_string()),
+            working_dir: PathBuf::from("/tmp"),
+            schedule_id: None,
+            execution_mode: None,
+            max_turns: None,
+            retry_config: None,
+            max_history_messages: None,
+        };
+        let transcripts = TranscriptStore::new(store.clone(), Some(&session));
+        assert!(transcripts
+            .save(&results(), TranscriptSummary::PerTask)
+            .await
+            .unwrap());
+
+        let messages = store.read_messages("parent").unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages.last().unwrap().as_concat_text(),
+            "Subagent task task-2 Failed: recipe not found"
+        );
+        assert_eq!(store.read_metadata("parent").unwrap().description, "Parent");
+        // No other session is created
+        assert_eq!(
+            store
+                .list(SortOrder::Descending, ModifiedRange::default())
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert!(!transcripts
+            .save(&[], TranscriptSummary::PerTask)
+            .await
+            .unwrap());
+        assert!(!TranscriptStore::new(store, None)
+            .save(&results(), TranscriptSummary::PerTask)
+            .await
+            .unwrap());
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use tokio::time::Instant;
 
 use crate::agents::subagent_execution_tool::task_types::{TaskInfo, TaskStatus};
 
@@ -9,18 +10,51 @@ pub fn get_task_name(task_info: &TaskInfo) -> &str {
         .unwrap_or(&task_info.task.id)
 }
 
-pub fn count_by_status(tasks: &HashMap<String, TaskInfo>) -> (usize, usize, usize, usize, usize) {
+pub fn count_by_status(
+    tasks: &HashMap<String, TaskInfo>,
+) -> (usize, usize, usize, usize, usize, usize) {
     let total = tasks.len();
-    let (pending, running, completed, failed) = tasks.values().fold(
-        (0, 0, 0, 0),
-        |(pending, running, completed, failed), task| match task.status {
-            TaskStatus::Pending => (pending + 1, running, completed, failed),
-            TaskStatus::Running => (pending, running + 1, completed, failed),
-            TaskStatus::Completed => (pending, running, completed + 1, failed),
-            TaskStatus::Failed => (pending, running, completed, failed + 1),
+    let (pending, running, completed, failed, cancelled) = tasks.values().fold(
+        (0, 0, 0, 0, 0),
+        |(pending, running, completed, failed, cancelled), task| match task.status {
+            TaskStatus::Pending => (pending + 1, running, completed, failed, cancelled),
+            TaskStatus::Running => (pending, running + 1, completed, failed, cancelled),
+            TaskStatus::Completed => (pending, running, completed + 1, failed, cancelled),
+            TaskStatus::Failed => (pending, running, completed, failed + 1, cancelled),
+            TaskStatus::Cancelled => (pending, running, completed, failed, cancelled + 1),
         },
     );
-    (total, pending, running, completed, failed)
+    (total, pending, running, completed, failed, cancelled)
+}
+
+/// Seconds since the first task started, and a simple estimate of the seconds left: the
+/// average duration of finished tasks times the number of unfinished ones. The estimate
+/// is None until a task has finished.
+pub fn batch_timing(tasks: &HashMap<String, TaskInfo>, now: Instant) -> (Option<f64>, Option<f64>) {
+    let elapsed_secs = tasks
+        .values()
+        .filter_map(|task| task.start_time)
+        .min()
+        .map(|start| now.duration_since(start).as_secs_f64());
+
+    let durations: Vec<f64> = tasks
+        .values()
+        .filter_map(|task| {
+            Some(
+                task.end_time?
+                    .duration_since(task.start_time?)
+                    .as_secs_f64(),
+            )
+        })
+        .collect();
+    let remaining = tasks
+        .values()
+        .filter(|task| matches!(task.status, TaskStatus::Pending | TaskStatus::Running))
+        .count();
+    let eta_secs = (!durations.is_empty())
+        .then(|| durations.iter().sum::<f64>() / durations.len() as f64 * remaining as f64);
+
+    (elapsed_secs, eta_secs)
 }
 
 pub fn strip_ansi_codes(text: &str) -> String {
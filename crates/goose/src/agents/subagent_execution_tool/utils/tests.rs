@@ -1,6 +1,6 @@
 use crate::agents::subagent_execution_tool::task_types::{Task, TaskInfo, TaskStatus, TaskType};
 use crate::agents::subagent_execution_tool::utils::{
-    count_by_status, get_task_name, strip_ansi_codes,
+    batch_timing, count_by_status, get_task_name, strip_ansi_codes,
 };
 use serde_json::json;
 use std::collections::HashMap;
@@ -30,6 +30,8 @@ mod test_get_task_name {
                     "recipe_path": "/path/to/recipe"
                 }
             }),
+            group: None,
+            tags: Vec::new(),
         };
 
         let task_info = create_task_info_with_defaults(sub_recipe_task, TaskStatus::Pending);
@@ -43,6 +45,8 @@ mod test_get_task_name {
             id: "task_2".to_string(),
             task_type: TaskType::InlineRecipe,
             payload: json!({"recipe": {"instructions": "do something"}}),
+            group: None,
+            tags: Vec::new(),
         };
 
         let task_info = create_task_info_with_defaults(inline_task, TaskStatus::Pending);
@@ -61,6 +65,8 @@ mod test_get_task_name {
                     // missing "name" field
                 }
             }),
+            group: None,
+            tags: Vec::new(),
         };
 
         let task_info = create_task_info_with_defaults(malformed_task, TaskStatus::Pending);
@@ -74,6 +80,8 @@ mod test_get_task_name {
             id: "task_4".to_string(),
             task_type: TaskType::SubRecipe,
             payload: json!({}), // missing "sub_recipe" field
+            group: None,
+            tags: Vec::new(),
         };
 
         let task_info = create_task_info_with_defaults(malformed_task, TaskStatus::Pending);
@@ -90,6 +98,8 @@ mod count_by_status {
             id: id.to_string(),
             task_type: TaskType::InlineRecipe,
             payload: json!({}),
+            group: None,
+            tags: Vec::new(),
         };
         create_task_info_with_defaults(task, status)
     }
@@ -97,10 +107,10 @@ mod count_by_status {
     #[test]
     fn counts_empty_map() {
         let tasks = HashMap::new();
-        let (total, pending, running, completed, failed) = count_by_status(&tasks);
+        let (total, pending, running, completed, failed, cancelled) = count_by_status(&tasks);
         assert_eq!(
-            (total, pending, running, completed, failed),
-            (0, 0, 0, 0, 0)
+            (total, pending, running, completed, failed, cancelled),
+            (0, 0, 0, 0, 0, 0)
         );
     }
 
@@ -116,10 +126,10 @@ mod count_by_status {
             create_test_task("task2", TaskStatus::Pending),
         );
 
-        let (total, pending, running, completed, failed) = count_by_status(&tasks);
+        let (total, pending, running, completed, failed, cancelled) = count_by_status(&tasks);
         assert_eq!(
-            (total, pending, running, completed, failed),
-            (2, 2, 0, 0, 0)
+            (total, pending, running, completed, failed, cancelled),
+            (2, 2, 0, 0, 0, 0)
         );
     }
 
@@ -146,15 +156,79 @@ mod count_by_status {
             "task5".to_string(),
             create_test_task("task5", TaskStatus::Completed),
         );
+        tasks.insert(
+            "task6".to_string(),
+            create_test_task("task6", TaskStatus::Cancelled),
+        );
 
-        let (total, pending, running, completed, failed) = count_by_status(&tasks);
+        let (total, pending, running, completed, failed, cancelled) = count_by_status(&tasks);
         assert_eq!(
-            (total, pending, running, completed, failed),
-            (5, 1, 1, 2, 1)
+            (total, pending, running, completed, failed, cancelled),
+            (6, 1, 1, 2, 1, 1)
         );
     }
 }
 
+mod batch_timing {
+    use super::*;
+    use tokio::time::{Duration, Instant};
+
+    fn create_timed_task(
+        id: &str,
+        status: TaskStatus,
+        start_time: Option<Instant>,
+        end_time: Option<Instant>,
+    ) -> (String, TaskInfo) {
+        let task = Task {
+            id: id.to_string(),
+            task_type: TaskType::InlineRecipe,
+            payload: json!({}),
+            group: None,
+            tags: Vec::new(),
+        };
+        let mut task_info = create_task_info_with_defaults(task, status);
+        task_info.start_time = start_time;
+        task_info.end_time = end_time;
+        (id.to_string(), task_info)
+    }
+
+    #[test]
+    fn no_timing_before_any_task_starts() {
+        let tasks = HashMap::from([create_timed_task("task1", TaskStatus::Pending, None, None)]);
+        assert_eq!(batch_timing(&tasks, Instant::now()), (None, None));
+    }
+
+    #[test]
+    fn estimates_from_finished_tasks() {
+        let now = Instant::now();
+        let secs_ago = |secs| Some(now - Duration::from_secs(secs));
+        let tasks = HashMap::from([
+            create_timed_task("task1", TaskStatus::Completed, secs_ago(30), secs_ago(20)),
+            create_timed_task("task2", TaskStatus::Failed, secs_ago(20), secs_ago(0)),
+            create_timed_task("task3", TaskStatus::Running, secs_ago(5), None),
+            create_timed_task("task4", TaskStatus::Pending, None, None),
+        ]);
+
+        let (elapsed, eta) = batch_timing(&tasks, now);
+        assert_eq!(elapsed, Some(30.0));
+        // Average of 10s and 20s, for two unfinished tasks
+        assert_eq!(eta, Some(30.0));
+    }
+
+    #[test]
+    fn no_eta_until_a_task_finishes() {
+        let now = Instant::now();
+        let tasks = HashMap::from([create_timed_task(
+            "task1",
+            TaskStatus::Running,
+            Some(now - Duration::from_secs(3)),
+            None,
+        )]);
+
+        assert_eq!(batch_timing(&tasks, now), (Some(3.0), None));
+    }
+}
+
 mod strip_ansi_codes {
     use super::*;
 
@@ -94,4 +94,7 @@ pub struct SessionConfig {
     /// Retry configuration for automated validation and recovery
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_config: Option<RetryConfig>,
+    /// Maximum number of recent messages sent to the provider on each turn
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_history_messages: Option<usize>,
 }
@@ -0,0 +1,162 @@
+use rmcp::model::{
+    LoggingLevel, LoggingMessageNotification, LoggingMessageNotificationMethod,
+    LoggingMessageNotificationParam, ServerNotification,
+};
+use serde_json::json;
+
+use crate::config::Config;
+use crate::providers::pricing::PricingInfo;
+use crate::session::SessionMetadata;
+
+/// Notification type used for usage threshold warnings
+pub const USAGE_ALERT_NOTIFICATION_TYPE: &str = "usage_alert";
+
+/// Token totals accumulated over a session
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AccumulatedUsage {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+}
+
+impl AccumulatedUsage {
+    pub fn from_metadata(metadata: &SessionMetadata) -> Self {
+        let tokens = |value: Option<i32>| value.unwrap_or(0).max(0) as i64;
+        Self {
+            input_tokens: tokens(metadata.accumulated_input_tokens),
+            output_tokens: tokens(metadata.accumulated_output_tokens),
+            total_tokens: tokens(metadata.accumulated_total_tokens),
+        }
+    }
+
+    fn cost_usd(&self, pricing: &PricingInfo) -> f64 {
+        pricing.input_cost * self.input_tokens as f64
+            + pricing.output_cost * self.output_tokens as f64
+    }
+}
+
+/// Session usage levels that trigger a warning when crossed. These never stop the
+/// agent, they only give the user a chance to intervene in a runaway loop.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageThresholds {
+    /// Warn once the session's accumulated tokens reach this count
+    pub tokens: Option<i64>,
+    /// Warn once the session's estimated cost in USD reaches this amount
+    pub cost_usd: Option<f64>,
+}
+
+impl UsageThresholds {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            tokens: config.get_param("GOOSE_USAGE_WARN_TOKENS").ok(),
+            cost_usd: config.get_param("GOOSE_USAGE_WARN_COST").ok(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_none() && self.cost_usd.is_none()
+    }
+
+    /// Warnings for the thresholds crossed by going from `before` to `after`. Cost is
+    /// only checked when pricing is known for the model.
+    pub fn crossed(
+        &self,
+        before: &AccumulatedUsage,
+        after: &AccumulatedUsage,
+        pricing: Option<&PricingInfo>,
+    ) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if let Some(limit) = self.tokens {
+            if before.total_tokens < limit && after.total_tokens >= limit {
+                warnings.push(format!(
+                    "This session has used {} tokens, passing the warning threshold of {}.",
+                    after.total_tokens, limit
+                ));
+            }
+        }
+
+        if let (Some(limit), Some(pricing)) = (self.cost_usd, pricing) {
+            let (cost_before, cost_after) = (before.cost_usd(pricing), after.cost_usd(pricing));
+            if cost_before < limit && cost_after >= limit {
+                warnings.push(format!(
+                    "This session's estimated cost is ${:.2}, passing the warning threshold of ${:.2}.",
+                    cost_after, limit
+                ));
+            }
+        }
+
+        warnings
+    }
+}
+
+pub fn usage_alert_notification(message: String) -> ServerNotification {
+    ServerNotification::LoggingMessageNotification(LoggingMessageNotification {
+        method: LoggingMessageNotificationMethod,
+        params: LoggingMessageNotificationParam {
+            data: json!({
+                "type": USAGE_ALERT_NOTIFICATION_TYPE,
+                "message": message,
+            }),
+            level: LoggingLevel::Warning,
+            logger: None,
+        },
+        extensions: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input_tokens: i64, output_tokens: i64) -> AccumulatedUsage {
+        AccumulatedUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+        }
+    }
+
+    #[test]
+    fn test_token_threshold_fires_once() {
+        let thresholds = UsageThresholds {
+            tokens: Some(1000),
+            cost_usd: None,
+        };
+
+        assert!(thresholds
+            .crossed(&usage(100, 100), &usage(400, 400), None)
+            .is_empty());
+        assert_eq!(
+            thresholds
+                .crossed(&usage(400, 400), &usage(600, 500), None)
+                .len(),
+            1
+        );
+        // Already past the threshold, so no repeated warning
+        assert!(thresholds
+            .crossed(&usage(600, 500), &usage(900, 700), None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cost_threshold_needs_pricing() {
+        let thresholds = UsageThresholds {
+            tokens: None,
+            cost_usd: Some(1.0),
+        };
+        let pricing = PricingInfo {
+            input_cost: 0.000_003,
+            output_cost: 0.000_015,
+            context_length: None,
+        };
+
+        let before = usage(100_000, 20_000);
+        let after = usage(200_000, 40_000);
+        assert!(thresholds.crossed(&before, &after, None).is_empty());
+
+        let warnings = thresholds.crossed(&before, &after, Some(&pricing));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("$1.20"));
+    }
+}
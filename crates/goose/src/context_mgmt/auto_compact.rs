@@ -290,6 +290,7 @@ mod tests {
             accumulated_output_tokens: Some(50),
             extension_data: crate::session::ExtensionData::new(),
             recipe: None,
+            last_activity: None,
         }
     }
 
@@ -2,5 +2,6 @@ pub mod auto_compact;
 mod common;
 pub mod summarize;
 pub mod truncate;
+pub mod window;
 
 pub use common::*;
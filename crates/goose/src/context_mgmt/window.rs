@@ -0,0 +1,83 @@
+use crate::conversation::message::Message;
+use rmcp::model::Role;
+use tracing::debug;
+
+// A window may only start at a plain user message, so it never opens with an
+// assistant turn or with tool responses whose requests were dropped
+fn is_window_boundary(message: &Message) -> bool {
+    message.role == Role::User && !message.is_tool_response()
+}
+
+/// Returns the most recent messages that fit within `max_messages`.
+///
+/// Older messages are dropped deterministically: the cut is moved forward to the next
+/// plain user message so tool requests and responses stay paired. If the window holds
+/// no such message, the cut moves back to the nearest earlier one instead, so the
+/// result may exceed the limit rather than send the provider a broken conversation.
+pub fn window_messages(messages: &[Message], max_messages: usize) -> &[Message] {
+    if messages.len() <= max_messages {
+        return messages;
+    }
+
+    let cut = messages.len() - max_messages;
+    let start = (cut..messages.len())
+        .find(|&i| is_window_boundary(&messages[i]))
+        .or_else(|| (0..cut).rev().find(|&i| is_window_boundary(&messages[i])))
+        .unwrap_or(0);
+
+    if start > 0 {
+        debug!(
+            "Dropping {} of {} messages to fit the history window of {}",
+            start,
+            messages.len(),
+            max_messages
+        );
+    }
+
+    &messages[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_core::tool::ToolCall;
+    use rmcp::model::Content;
+    use serde_json::json;
+
+    fn conversation() -> Vec<Message> {
+        vec![
+            Message::user().with_text("first question"),
+            Message::assistant().with_text("first answer"),
+            Message::user().with_text("second question"),
+            Message::assistant()
+                .with_tool_request("1", Ok(ToolCall::new("read", json!({"path": "a"})))),
+            Message::user().with_tool_response("1", Ok(vec![Content::text("contents")])),
+            Message::assistant().with_text("second answer"),
+        ]
+    }
+
+    #[test]
+    fn test_window_keeps_short_history() {
+        let messages = conversation();
+        assert_eq!(window_messages(&messages, 10).len(), 6);
+        assert_eq!(window_messages(&messages, 6).len(), 6);
+    }
+
+    #[test]
+    fn test_window_starts_at_user_message() {
+        let messages = conversation();
+        // The last five messages start with an assistant turn, so the cut moves forward
+        let window = window_messages(&messages, 5);
+        assert_eq!(window.len(), 4);
+        assert_eq!(window[0].as_concat_text(), "second question");
+    }
+
+    #[test]
+    fn test_window_never_orphans_tool_responses() {
+        let messages = conversation();
+        // Only the tool exchange fits, so the window extends back to its question
+        let window = window_messages(&messages, 2);
+        assert_eq!(window.len(), 4);
+        assert_eq!(window[0].as_concat_text(), "second question");
+    }
+}
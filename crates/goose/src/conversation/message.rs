@@ -364,6 +364,16 @@ impl From<PromptMessage> for Message {
     }
 }
 
+/// What a streamed message chunk carries, so clients can render reasoning separately
+/// from the answer
+#[derive(ToSchema, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    Reasoning,
+    Answer,
+    ToolCall,
+}
+
 #[derive(ToSchema, Clone, PartialEq, Serialize, Deserialize)]
 /// A message to or from an LLM
 #[serde(rename_all = "camelCase")]
@@ -576,6 +586,31 @@ impl Message {
             .collect()
     }
 
+    /// The kind of content the message carries. Tool calls take precedence over answer
+    /// text, which takes precedence over reasoning; messages with none of these have no kind.
+    pub fn content_kind(&self) -> Option<ContentKind> {
+        let has = |kind: fn(&MessageContent) -> bool| self.content.iter().any(kind);
+        if has(|c| {
+            matches!(
+                c,
+                MessageContent::ToolRequest(_) | MessageContent::FrontendToolRequest(_)
+            )
+        }) {
+            Some(ContentKind::ToolCall)
+        } else if has(|c| matches!(c, MessageContent::Text(_))) {
+            Some(ContentKind::Answer)
+        } else if has(|c| {
+            matches!(
+                c,
+                MessageContent::Thinking(_) | MessageContent::RedactedThinking(_)
+            )
+        }) {
+            Some(ContentKind::Reasoning)
+        } else {
+            None
+        }
+    }
+
     /// Check if the message has only TextContent
     pub fn has_only_text_content(&self) -> bool {
         self.content
@@ -932,4 +967,24 @@ mod tests {
 
         assert_eq!(message.as_concat_text(), "Hello world 世界 🌍");
     }
+
+    #[test]
+    fn test_message_content_kind() {
+        use crate::conversation::message::ContentKind;
+
+        let reasoning = Message::assistant().with_thinking("Let me check", "");
+        assert_eq!(reasoning.content_kind(), Some(ContentKind::Reasoning));
+
+        let answer = Message::assistant()
+            .with_thinking("Let me check", "")
+            .with_text("It's fine");
+        assert_eq!(answer.content_kind(), Some(ContentKind::Answer));
+
+        let tool_call = Message::assistant()
+            .with_text("Running it")
+            .with_tool_request("id", Ok(ToolCall::new("shell", json!({}))));
+        assert_eq!(tool_call.content_kind(), Some(ContentKind::ToolCall));
+
+        assert_eq!(Message::assistant().content_kind(), None);
+    }
 }
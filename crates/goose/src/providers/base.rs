@@ -211,11 +211,18 @@ impl ConfigKey {
 pub struct ProviderUsage {
     pub model: String,
     pub usage: Usage,
+    /// Whether the response was replayed from the development response cache
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cache_hit: bool,
 }
 
 impl ProviderUsage {
     pub fn new(model: String, usage: Usage) -> Self {
-        Self { model, usage }
+        Self {
+            model,
+            usage,
+            cache_hit: false,
+        }
     }
 
     /// Ensures this ProviderUsage has token counts, estimating them if necessary
@@ -243,6 +250,7 @@ impl ProviderUsage {
         ProviderUsage {
             model: self.model.clone(),
             usage: self.usage + other.usage,
+            cache_hit: self.cache_hit && other.cache_hit,
         }
     }
 }
@@ -378,6 +386,12 @@ pub trait Provider: Send + Sync {
         RetryConfig::default()
     }
 
+    /// How long a whole request may take before it fails with `ProviderError::Timeout`.
+    /// Defaults to GOOSE_PROVIDER_TIMEOUT; providers can override it with their own limit.
+    fn request_timeout(&self) -> Option<std::time::Duration> {
+        super::timeout::provider_timeout_from_config()
+    }
+
     /// Optional hook to fetch supported models.
     async fn fetch_supported_models(&self) -> Result<Option<Vec<String>>, ProviderError> {
         Ok(None)
@@ -1,6 +1,9 @@
+use super::base::Provider;
+use crate::config::Config;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingRequest {
@@ -18,7 +21,225 @@ pub struct EmbeddingData {
     pub embedding: Vec<f32>,
 }
 
+/// Turns text into vectors for similarity search. Providers with an embedding endpoint
+/// implement it, as does `LocalEmbedder`; features needing embeddings should get one from
+/// `configured_embedder` rather than calling a provider themselves.
+///
+/// Vectors from different embedders are not comparable, so only compare vectors made by
+/// the same one.
 #[async_trait]
-pub trait EmbeddingCapable {
+pub trait EmbeddingCapable: Send + Sync {
+    /// One vector per text, in the same order
     async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>>;
 }
+
+/// Embeds with a provider's embedding endpoint
+pub struct ProviderEmbedder {
+    provider: Arc<dyn Provider>,
+}
+
+impl ProviderEmbedder {
+    pub fn new(provider: Arc<dyn Provider>) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl EmbeddingCapable for ProviderEmbedder {
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        Ok(self.provider.create_embeddings(texts).await?)
+    }
+}
+
+/// Default number of dimensions produced by the local embedder
+pub const LOCAL_EMBEDDING_DIMENSIONS: usize = 256;
+
+/// Embeds without any network calls by hashing words and character trigrams into a fixed
+/// size vector. Much weaker than a model, but good enough to rank text that shares
+/// vocabulary, and always available.
+#[derive(Debug, Clone, Copy)]
+pub struct LocalEmbedder {
+    dimensions: usize,
+}
+
+impl Default for LocalEmbedder {
+    fn default() -> Self {
+        Self::new(LOCAL_EMBEDDING_DIMENSIONS)
+    }
+}
+
+impl LocalEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    pub fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        let text = text.to_lowercase();
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            vector[fnv1a(word.as_bytes()) as usize % self.dimensions] += 1.0;
+
+            let padded: Vec<char> = format!(" {} ", word).chars().collect();
+            for trigram in padded.windows(3) {
+                let trigram: String = trigram.iter().collect();
+                vector[fnv1a(trigram.as_bytes()) as usize % self.dimensions] += 0.5;
+            }
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+}
+
+#[async_trait]
+impl EmbeddingCapable for LocalEmbedder {
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_text(text)).collect())
+    }
+}
+
+// Stable across platforms and Rust versions, unlike the std hasher, so stored local
+// embeddings stay valid
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Pick the embedder for a feature to use.
+///
+/// GOOSE_EMBEDDING_BACKEND chooses: `provider` requires the provider to support
+/// embeddings, `local` always uses the local embedder, and anything else (the default)
+/// uses the provider when it can embed and the local embedder otherwise.
+pub fn configured_embedder(
+    provider: Option<Arc<dyn Provider>>,
+) -> Result<Arc<dyn EmbeddingCapable>> {
+    let backend: String = Config::global()
+        .get_param("GOOSE_EMBEDDING_BACKEND")
+        .unwrap_or_else(|_| "auto".to_string());
+    let provider = provider.filter(|provider| provider.supports_embeddings());
+
+    match (backend.to_lowercase().as_str(), provider) {
+        ("local", _) => Ok(Arc::new(LocalEmbedder::default())),
+        ("provider", None) => Err(anyhow::anyhow!(
+            "GOOSE_EMBEDDING_BACKEND is 'provider' but the configured provider does not support embeddings"
+        )),
+        (_, Some(provider)) => Ok(Arc::new(ProviderEmbedder::new(provider))),
+        (_, None) => Ok(Arc::new(LocalEmbedder::default())),
+    }
+}
+
+/// Rank `texts` by how similar they are to `query`, most similar first, as pairs of each
+/// text's index and its similarity
+pub async fn rank_by_similarity(
+    embedder: &dyn EmbeddingCapable,
+    query: &str,
+    texts: Vec<String>,
+) -> Result<Vec<(usize, f32)>> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let count = texts.len();
+    let mut vectors = embedder
+        .create_embeddings(std::iter::once(query.to_string()).chain(texts).collect())
+        .await?;
+    if vectors.len() != count + 1 {
+        return Err(anyhow::anyhow!(
+            "Expected {} embeddings but got {}",
+            count + 1,
+            vectors.len()
+        ));
+    }
+    let query = vectors.remove(0);
+
+    let mut ranked: Vec<(usize, f32)> = vectors
+        .iter()
+        .map(|vector| cosine_similarity(&query, vector))
+        .enumerate()
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(ranked)
+}
+
+/// Cosine similarity of two vectors, 0.0 when either is empty, zero or they differ in length
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_embedder_ranks_shared_vocabulary() {
+        let embedder = LocalEmbedder::default();
+        let vectors = embedder
+            .create_embeddings(vec![
+                "run the database migrations".to_string(),
+                "Database migration failed".to_string(),
+                "bake a chocolate cake".to_string(),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(vectors.len(), 3);
+        assert!(vectors
+            .iter()
+            .all(|v| v.len() == LOCAL_EMBEDDING_DIMENSIONS));
+        let related = cosine_similarity(&vectors[0], &vectors[1]);
+        let unrelated = cosine_similarity(&vectors[0], &vectors[2]);
+        assert!(related > unrelated);
+
+        // Deterministic, so stored vectors can be compared with new ones
+        assert_eq!(
+            embedder.embed_text("run the database migrations"),
+            vectors[0]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rank_by_similarity() {
+        let texts = vec![
+            "bake a chocolate cake".to_string(),
+            "Database migration failed".to_string(),
+        ];
+        let ranked = rank_by_similarity(&LocalEmbedder::default(), "database migrations", texts)
+            .await
+            .unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].0, 1);
+        assert!(ranked[0].1 > ranked[1].1);
+
+        assert!(
+            rank_by_similarity(&LocalEmbedder::default(), "anything", Vec::new())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cosine_similarity_edge_cases() {
+        assert_eq!(cosine_similarity(&[], &[]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]) - 1.0).abs() < 1e-6);
+    }
+}
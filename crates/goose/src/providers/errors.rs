@@ -18,6 +18,9 @@ pub enum ProviderError {
     #[error("Request failed: {0}")]
     RequestFailed(String),
 
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+
     #[error("Execution error: {0}")]
     ExecutionError(String),
 
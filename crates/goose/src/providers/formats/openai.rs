@@ -36,6 +36,19 @@ struct Delta {
     content: Option<String>,
     role: Option<String>,
     tool_calls: Option<Vec<DeltaToolCall>>,
+    // Reasoning models on OpenAI-compatible APIs stream their thinking separately from
+    // the answer, as `reasoning_content` (DeepSeek, vLLM) or `reasoning` (OpenRouter)
+    reasoning_content: Option<String>,
+    reasoning: Option<String>,
+}
+
+impl Delta {
+    fn reasoning_text(&self) -> Option<&str> {
+        self.reasoning_content
+            .as_deref()
+            .or(self.reasoning.as_deref())
+            .filter(|text| !text.is_empty())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -442,10 +455,7 @@ where
 
             let usage = chunk.usage.as_ref().and_then(|u| {
                 chunk.model.as_ref().map(|model| {
-                    ProviderUsage {
-                        usage: get_usage(u),
-                        model: model.clone(),
-                    }
+                    ProviderUsage::new(model.clone(), get_usage(u))
                 })
             });
 
@@ -543,6 +553,20 @@ where
                     }),
                     usage,
                 )
+            } else if let Some(reasoning) = chunk.choices[0]
+                .delta
+                .reasoning_text()
+                .filter(|_| chunk.choices[0].delta.content.as_deref().is_none_or(str::is_empty))
+            {
+                yield (
+                    Some(Message {
+                        id: chunk.id.clone(),
+                        role: Role::Assistant,
+                        created: chrono::Utc::now().timestamp(),
+                        content: vec![MessageContent::thinking(reasoning, "")],
+                    }),
+                    None,
+                )
             } else if let Some(text) = &chunk.choices[0].delta.content {
                 yield (
                     Some(Message {
@@ -664,7 +688,7 @@ pub fn create_request(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::conversation::message::Message;
+    use crate::conversation::message::{ContentKind, Message};
     use rmcp::object;
     use serde_json::json;
     use tokio::pin;
@@ -1214,4 +1238,34 @@ data: [DONE]
 
         panic!("Expected tool call message with two calls, but did not see it");
     }
+
+    #[tokio::test]
+    async fn test_streamed_reasoning_is_separate_from_answer() -> anyhow::Result<()> {
+        let response_lines = r#"
+data: {"model":"deepseek-reasoner","choices":[{"delta":{"role":"assistant","content":null,"reasoning_content":"The user wants"},"index":0,"finish_reason":null}],"id":"chunk-1","created":1753288340}
+data: {"model":"deepseek-reasoner","choices":[{"delta":{"role":"assistant","content":"","reasoning":" a greeting."},"index":0,"finish_reason":null}],"id":"chunk-1","created":1753288340}
+data: {"model":"deepseek-reasoner","choices":[{"delta":{"role":"assistant","content":"Hello!"},"index":0,"finish_reason":"stop"}],"id":"chunk-1","created":1753288341}
+data: [DONE]
+"#;
+
+        let response_stream =
+            tokio_stream::iter(response_lines.lines().map(|line| Ok(line.to_string())));
+        let messages = response_to_streaming_message(response_stream);
+        pin!(messages);
+
+        let mut kinds = Vec::new();
+        while let Some(Ok((message, _usage))) = messages.next().await {
+            if let Some(message) = message {
+                kinds.push((message.content_kind(), message.content[0].clone()));
+            }
+        }
+
+        assert_eq!(kinds.len(), 3);
+        assert_eq!(kinds[0].0, Some(ContentKind::Reasoning));
+        assert_eq!(kinds[0].1, MessageContent::thinking("The user wants", ""));
+        assert_eq!(kinds[1].1, MessageContent::thinking(" a greeting.", ""));
+        assert_eq!(kinds[2].0, Some(ContentKind::Answer));
+        assert_eq!(kinds[2].1, MessageContent::text("Hello!"));
+        Ok(())
+    }
 }
@@ -6,6 +6,7 @@ use tokio::sync::Mutex;
 
 use super::base::{LeadWorkerProviderTrait, Provider, ProviderMetadata, ProviderUsage};
 use super::errors::ProviderError;
+use super::timeout::with_request_timeout;
 use crate::conversation::message::{Message, MessageContent};
 use crate::model::ModelConfig;
 use rmcp::model::Tool;
@@ -326,6 +327,12 @@ impl Provider for LeadWorkerProvider {
         self.lead_provider.get_model_config()
     }
 
+    // Each wrapped provider's own timeout applies instead, so a stalled worker can still
+    // fall back to the lead provider
+    fn request_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
     async fn complete_with_model(
         &self,
         _model_config: &ModelConfig,
@@ -378,7 +385,11 @@ impl Provider for LeadWorkerProvider {
         }
 
         // Make the completion request
-        let result = provider.complete(system, messages, tools).await;
+        let result = with_request_timeout(
+            provider.request_timeout(),
+            provider.complete(system, messages, tools),
+        )
+        .await;
 
         // For technical failures, try with default model (lead provider) instead
         let final_result = match &result {
@@ -386,7 +397,11 @@ impl Provider for LeadWorkerProvider {
                 tracing::warn!("Technical failure with {} provider, retrying with default model (lead provider)", provider_type);
 
                 // Try with lead provider as the default/fallback for technical failures
-                let default_result = self.lead_provider.complete(system, messages, tools).await;
+                let default_result = with_request_timeout(
+                    self.lead_provider.request_timeout(),
+                    self.lead_provider.complete(system, messages, tools),
+                )
+                .await;
 
                 match &default_result {
                     Ok(_) => {
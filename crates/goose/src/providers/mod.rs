@@ -25,11 +25,13 @@ pub mod openai;
 pub mod openrouter;
 pub mod pricing;
 pub mod provider_registry;
+pub mod response_cache;
 mod retry;
 pub mod sagemaker_tgi;
 pub mod snowflake;
 pub mod testprovider;
 pub mod tetrate;
+pub mod timeout;
 pub mod toolshim;
 pub mod usage_estimator;
 pub mod utils;
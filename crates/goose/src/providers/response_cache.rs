@@ -0,0 +1,145 @@
+use anyhow::Result;
+use etcetera::{choose_app_strategy, AppStrategy};
+use rmcp::model::Tool;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+use super::base::ProviderUsage;
+use crate::config::APP_STRATEGY;
+use crate::conversation::message::Message;
+
+/// Development-only cache of provider responses, keyed by everything sent to the
+/// provider. Identical prompts are replayed from disk instead of calling the provider.
+///
+/// Enabled with GOOSE_RESPONSE_CACHE=true. Responses are stored under the goose cache
+/// dir unless GOOSE_RESPONSE_CACHE_DIR is set. Never enable this outside of development,
+/// since cached responses are returned without contacting the live provider.
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    message: Message,
+    usage: ProviderUsage,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The cache configured by the environment, or None when it hasn't been opted into
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var("GOOSE_RESPONSE_CACHE")
+            .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true"));
+        if !enabled {
+            return None;
+        }
+
+        let dir = match std::env::var("GOOSE_RESPONSE_CACHE_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => choose_app_strategy(APP_STRATEGY.clone())
+                .ok()?
+                .cache_dir()
+                .join("responses"),
+        };
+        Some(Self::new(dir))
+    }
+
+    /// Key for a request; message ids and timestamps are left out so re-running the same
+    /// prompt produces the same key
+    pub fn key(model: &str, system_prompt: &str, messages: &[Message], tools: &[Tool]) -> String {
+        let messages: Vec<_> = messages
+            .iter()
+            .map(|message| (&message.role, &message.content))
+            .collect();
+        let request = serde_json::json!({
+            "model": model,
+            "system": system_prompt,
+            "messages": messages,
+            "tools": tools,
+        });
+
+        let mut hasher = Sha256::new();
+        hasher.update(request.to_string().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// The cached response for `key`, with its usage marked as a cache hit
+    pub fn get(&self, key: &str) -> Option<(Message, ProviderUsage)> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        match serde_json::from_str::<CachedResponse>(&content) {
+            Ok(cached) => {
+                let mut usage = cached.usage;
+                usage.cache_hit = true;
+                Some((cached.message, usage))
+            }
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable cached response {}: {}", key, e);
+                None
+            }
+        }
+    }
+
+    pub fn put(&self, key: &str, message: &Message, usage: &ProviderUsage) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let cached = CachedResponse {
+            message: message.clone(),
+            usage: usage.clone(),
+        };
+        fs::write(self.path(key), serde_json::to_string(&cached)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::base::Usage;
+
+    #[test]
+    fn test_key_ignores_message_ids_and_timestamps() {
+        let first = vec![Message::user().with_text("hello")];
+        let mut second = vec![Message::user().with_text("hello")];
+        second[0].created += 60;
+        second[0].id = Some("different".to_string());
+
+        let key = ResponseCache::key("model", "system", &first, &[]);
+        assert_eq!(key, ResponseCache::key("model", "system", &second, &[]));
+        assert_ne!(
+            key,
+            ResponseCache::key("other-model", "system", &first, &[])
+        );
+        assert_ne!(
+            key,
+            ResponseCache::key("model", "other system", &first, &[])
+        );
+    }
+
+    #[test]
+    fn test_put_and_get_marks_cache_hit() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path().join("responses"));
+        let messages = vec![Message::user().with_text("hello")];
+        let key = ResponseCache::key("model", "system", &messages, &[]);
+        assert!(cache.get(&key).is_none());
+
+        let usage =
+            ProviderUsage::new("model".to_string(), Usage::new(Some(10), Some(5), Some(15)));
+        cache
+            .put(&key, &Message::assistant().with_text("hi there"), &usage)
+            .unwrap();
+
+        let (message, usage) = cache.get(&key).unwrap();
+        assert_eq!(message.as_concat_text(), "hi there");
+        assert_eq!(usage.usage.total_tokens, Some(15));
+        assert!(usage.cache_hit);
+    }
+}
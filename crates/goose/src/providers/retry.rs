@@ -89,7 +89,9 @@ pub trait ProviderRetry {
                 Err(error) => {
                     let should_retry = matches!(
                         error,
-                        ProviderError::RateLimitExceeded(_) | ProviderError::ServerError(_)
+                        ProviderError::RateLimitExceeded(_)
+                            | ProviderError::ServerError(_)
+                            | ProviderError::Timeout(_)
                     );
 
                     if should_retry && attempts < config.max_retries {
@@ -0,0 +1,99 @@
+use futures::StreamExt;
+use std::future::Future;
+use std::time::Duration;
+
+use super::base::MessageStream;
+use super::errors::ProviderError;
+use crate::config::Config;
+
+/// Seconds a whole provider request may take before it is abandoned, 0 or unset for no limit.
+///
+/// This wraps the entire completion (or the wait for the first streamed chunk) and is
+/// separate from the HTTP timeouts individual providers configure for their transport.
+pub const PROVIDER_TIMEOUT_CONFIG_KEY: &str = "GOOSE_PROVIDER_TIMEOUT";
+
+/// The request timeout configured with GOOSE_PROVIDER_TIMEOUT, if any
+pub fn provider_timeout_from_config() -> Option<Duration> {
+    Config::global()
+        .get_param::<u64>(PROVIDER_TIMEOUT_CONFIG_KEY)
+        .ok()
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+}
+
+fn timed_out(timeout: Duration) -> ProviderError {
+    ProviderError::Timeout(format!(
+        "the provider did not respond within {} seconds",
+        timeout.as_secs()
+    ))
+}
+
+/// Run a provider request, failing with `ProviderError::Timeout` if it takes longer than `timeout`
+pub async fn with_request_timeout<T>(
+    timeout: Option<Duration>,
+    request: impl Future<Output = Result<T, ProviderError>>,
+) -> Result<T, ProviderError> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, request)
+            .await
+            .map_err(|_| timed_out(timeout))?,
+        None => request.await,
+    }
+}
+
+/// Wait at most `timeout` for the first chunk of `stream`, so a provider that accepts a
+/// streaming request but never answers fails the same way as a stalled completion
+pub async fn first_chunk_within(
+    timeout: Option<Duration>,
+    mut stream: MessageStream,
+) -> Result<MessageStream, ProviderError> {
+    let Some(timeout) = timeout else {
+        return Ok(stream);
+    };
+    let first = tokio::time::timeout(timeout, stream.next())
+        .await
+        .map_err(|_| timed_out(timeout))?;
+    Ok(Box::pin(futures::stream::iter(first).chain(stream)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversation::message::Message;
+    use crate::providers::base::ProviderUsage;
+
+    #[tokio::test]
+    async fn test_with_request_timeout() {
+        let result = with_request_timeout(Some(Duration::from_millis(10)), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        })
+        .await;
+        assert!(matches!(result, Err(ProviderError::Timeout(_))));
+
+        let result = with_request_timeout(Some(Duration::from_secs(5)), async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_first_chunk_within() {
+        let stalled: MessageStream = Box::pin(futures::stream::pending::<
+            Result<(Option<Message>, Option<ProviderUsage>), ProviderError>,
+        >());
+        let result = first_chunk_within(Some(Duration::from_millis(10)), stalled).await;
+        assert!(matches!(result, Err(ProviderError::Timeout(_))));
+
+        let chunks: MessageStream = Box::pin(futures::stream::iter(vec![
+            Ok((Some(Message::assistant().with_text("one")), None)),
+            Ok((Some(Message::assistant().with_text("two")), None)),
+        ]));
+        let stream = first_chunk_within(Some(Duration::from_secs(5)), chunks)
+            .await
+            .unwrap();
+        let texts: Vec<String> = stream
+            .map(|chunk| chunk.unwrap().0.unwrap().as_concat_text())
+            .collect()
+            .await;
+        assert_eq!(texts, vec!["one", "two"]);
+    }
+}
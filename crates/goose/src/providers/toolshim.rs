@@ -33,6 +33,7 @@
 use super::errors::ProviderError;
 use super::ollama::OLLAMA_DEFAULT_PORT;
 use super::ollama::OLLAMA_HOST;
+use super::retry::RetryConfig;
 use crate::conversation::message::{Message, MessageContent};
 use crate::conversation::Conversation;
 use crate::model::ModelConfig;
@@ -49,6 +50,11 @@ use uuid::Uuid;
 /// Default model to use for tool interpretation
 pub const DEFAULT_INTERPRETER_MODEL_OLLAMA: &str = "mistral-nemo";
 
+/// Retries after a failed interpreter call before falling back to the original message
+pub const TOOLSHIM_MAX_RETRIES: usize = 2;
+const TOOLSHIM_INITIAL_RETRY_INTERVAL_MS: u64 = 500;
+const TOOLSHIM_MAX_RETRY_INTERVAL_MS: u64 = 4_000;
+
 /// Environment variables that affect behavior:
 /// - GOOSE_TOOLSHIM: When set to "true" or "1", enables using the tool shim in the standard OllamaProvider (default: false)
 /// - GOOSE_TOOLSHIM_OLLAMA_MODEL: Ollama model to use as the tool interpreter (default: DEFAULT_INTERPRETER_MODEL)
@@ -438,3 +444,110 @@ pub async fn augment_message_with_tool_calls<T: ToolInterpreter>(
 
     Ok(final_message)
 }
+
+/// Like `augment_message_with_tool_calls`, but retries a failing interpreter with backoff.
+///
+/// If the interpreter still fails after the retries, the original message is returned
+/// unchanged so the turn can continue without tool calls instead of aborting.
+pub async fn augment_message_with_retries<T: ToolInterpreter>(
+    interpreter: &T,
+    message: Message,
+    tools: &[Tool],
+    retry_config: &RetryConfig,
+) -> Message {
+    let mut attempt = 0;
+    loop {
+        match augment_message_with_tool_calls(interpreter, message.clone(), tools).await {
+            Ok(augmented) => return augmented,
+            Err(e) if attempt < retry_config.max_retries => {
+                attempt += 1;
+                tracing::warn!(
+                    "Tool interpreter failed, retrying ({}/{}): {}",
+                    attempt,
+                    retry_config.max_retries,
+                    e
+                );
+                tokio::time::sleep(retry_config.delay_for_attempt(attempt)).await;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Tool interpreter failed after {} retries, continuing without tool calls: {}",
+                    retry_config.max_retries,
+                    e
+                );
+                return message;
+            }
+        }
+    }
+}
+
+/// Retry settings used for the toolshim interpreter
+pub fn toolshim_retry_config() -> RetryConfig {
+    RetryConfig::new(
+        TOOLSHIM_MAX_RETRIES,
+        TOOLSHIM_INITIAL_RETRY_INTERVAL_MS,
+        2.0,
+        TOOLSHIM_MAX_RETRY_INTERVAL_MS,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Fails a fixed number of times before returning a single tool call
+    struct FlakyInterpreter {
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ToolInterpreter for FlakyInterpreter {
+        async fn interpret_to_tool_calls(
+            &self,
+            _content: &str,
+            _tools: &[Tool],
+        ) -> Result<Vec<ToolCall>, ProviderError> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(ProviderError::RequestFailed("interpreter hiccup".into()));
+            }
+            Ok(vec![ToolCall::new("shell", json!({"command": "ls"}))])
+        }
+    }
+
+    fn tools() -> Vec<Tool> {
+        vec![Tool::new(
+            "shell".to_string(),
+            "Run a command".to_string(),
+            serde_json::Map::new(),
+        )]
+    }
+
+    #[tokio::test]
+    async fn test_augment_message_with_retries() {
+        let retry_config = RetryConfig::new(2, 0, 1.0, 0);
+        let message = Message::assistant().with_text("Let me list the files");
+
+        let interpreter = FlakyInterpreter {
+            failures: 2,
+            calls: AtomicUsize::new(0),
+        };
+        let augmented =
+            augment_message_with_retries(&interpreter, message.clone(), &tools(), &retry_config)
+                .await;
+        assert_eq!(interpreter.calls.load(Ordering::SeqCst), 3);
+        assert!(augmented.is_tool_call());
+
+        // Once retries run out, the original message comes back untouched
+        let interpreter = FlakyInterpreter {
+            failures: 3,
+            calls: AtomicUsize::new(0),
+        };
+        let fallback =
+            augment_message_with_retries(&interpreter, message.clone(), &tools(), &retry_config)
+                .await;
+        assert_eq!(interpreter.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(fallback, message);
+    }
+}
@@ -154,6 +154,12 @@ pub struct SubRecipe {
     pub sequential_when_repeated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Environment variables set for each run of the sub-recipe, on top of goose's own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+    /// Absolute working directory each run of the sub-recipe starts in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
 }
 
 fn deserialize_value_map_as_string<'de, D>(
@@ -1222,6 +1222,7 @@ async fn run_scheduled_job_internal(
             execution_mode: job.execution_mode.clone(),
             max_turns: None,
             retry_config: None,
+            max_history_messages: None,
         };
 
         match agent
@@ -1300,6 +1301,7 @@ async fn run_scheduled_job_internal(
                             accumulated_output_tokens: None,
                             extension_data: crate::session::ExtensionData::new(),
                             recipe: None,
+                            last_activity: None,
                         };
                         if let Err(e_fb) = crate::session::storage::save_messages_with_metadata(
                             &session_file_path,
@@ -0,0 +1,273 @@
+use crate::conversation::Conversation;
+use crate::session::info::{
+    get_valid_sorted_sessions_in_range, sort_sessions, ModifiedRange, SessionInfo, SortOrder,
+};
+use crate::session::storage::{self, Identifier, SessionMetadata};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Where sessions are kept. `SessionStore` goes through a backend for every read and
+/// write, so routes and tools can run against something other than the session directory.
+///
+/// Every method taking a session id fails if the id is invalid.
+#[async_trait]
+pub trait SessionBackend: Send + Sync {
+    /// Where a session is stored, whether or not it exists yet
+    fn path(&self, session_id: &str) -> Result<PathBuf>;
+
+    fn exists(&self, session_id: &str) -> Result<bool>;
+
+    /// Sessions modified within `range`, skipping any whose metadata can't be read
+    fn list(&self, sort_order: SortOrder, range: ModifiedRange) -> Result<Vec<SessionInfo>>;
+
+    fn read_metadata(&self, session_id: &str) -> Result<SessionMetadata>;
+
+    fn read_messages(&self, session_id: &str) -> Result<Conversation>;
+
+    /// A token that changes whenever the session does, None if it is unknown
+    fn revision(&self, session_id: &str) -> Option<String>;
+
+    /// Create or replace a session
+    fn write(
+        &self,
+        session_id: &str,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<()>;
+
+    /// Create a session, returning false without writing anything if one with this id
+    /// already exists. The check and the creation can't be split by another writer.
+    fn create(
+        &self,
+        session_id: &str,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<bool>;
+
+    /// Replace a session's metadata, preserving its messages
+    async fn update_metadata(&self, session_id: &str, metadata: &SessionMetadata) -> Result<()>;
+
+    /// Delete a session, returning false if it did not exist
+    fn delete(&self, session_id: &str) -> Result<bool>;
+}
+
+/// Sessions stored as JSONL files in the session directory
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSessionBackend;
+
+#[async_trait]
+impl SessionBackend for FileSessionBackend {
+    fn path(&self, session_id: &str) -> Result<PathBuf> {
+        storage::get_path(Identifier::Name(session_id.to_string()))
+    }
+
+    fn exists(&self, session_id: &str) -> Result<bool> {
+        Ok(self.path(session_id)?.exists())
+    }
+
+    fn list(&self, sort_order: SortOrder, range: ModifiedRange) -> Result<Vec<SessionInfo>> {
+        get_valid_sorted_sessions_in_range(sort_order, range)
+    }
+
+    fn read_metadata(&self, session_id: &str) -> Result<SessionMetadata> {
+        storage::read_metadata(&self.path(session_id)?)
+    }
+
+    fn read_messages(&self, session_id: &str) -> Result<Conversation> {
+        storage::read_messages(&self.path(session_id)?)
+    }
+
+    fn revision(&self, session_id: &str) -> Option<String> {
+        file_revision(&self.path(session_id).ok()?)
+    }
+
+    fn write(
+        &self,
+        session_id: &str,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<()> {
+        storage::save_messages_with_metadata(&self.path(session_id)?, metadata, conversation)
+    }
+
+    fn create(
+        &self,
+        session_id: &str,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<bool> {
+        storage::create_session_file(&self.path(session_id)?, metadata, conversation)
+    }
+
+    async fn update_metadata(&self, session_id: &str, metadata: &SessionMetadata) -> Result<()> {
+        storage::update_metadata(&self.path(session_id)?, metadata).await
+    }
+
+    fn delete(&self, session_id: &str) -> Result<bool> {
+        storage::delete_session_file(&self.path(session_id)?)
+    }
+}
+
+/// Revision of a session file, derived from its modification time and size so it can be
+/// computed without reading the file
+pub fn file_revision(path: &Path) -> Option<String> {
+    let file = std::fs::metadata(path).ok()?;
+    let modified = file
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    Some(format!("{:x}-{:x}", modified.as_nanos(), file.len()))
+}
+
+struct MemorySession {
+    metadata: SessionMetadata,
+    conversation: Conversation,
+    modified: DateTime<Utc>,
+    revision: u64,
+}
+
+/// Sessions kept in memory, for tests and embedders that don't persist sessions
+#[derive(Default)]
+pub struct MemorySessionBackend {
+    sessions: RwLock<HashMap<String, MemorySession>>,
+    next_revision: AtomicU64,
+}
+
+impl MemorySessionBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sessions(&self) -> Result<RwLockReadGuard<'_, HashMap<String, MemorySession>>> {
+        self.sessions
+            .read()
+            .map_err(|_| anyhow::anyhow!("Session store lock poisoned"))
+    }
+
+    fn sessions_mut(&self) -> Result<RwLockWriteGuard<'_, HashMap<String, MemorySession>>> {
+        self.sessions
+            .write()
+            .map_err(|_| anyhow::anyhow!("Session store lock poisoned"))
+    }
+
+    fn new_session(
+        &self,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> MemorySession {
+        MemorySession {
+            metadata: metadata.clone(),
+            conversation: conversation.clone(),
+            modified: Utc::now(),
+            revision: self.next_revision.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    fn with_session<T>(
+        &self,
+        session_id: &str,
+        read: impl FnOnce(&MemorySession) -> T,
+    ) -> Result<T> {
+        storage::validate_session_name(session_id)?;
+        let sessions = self.sessions()?;
+        sessions
+            .get(session_id)
+            .map(read)
+            .ok_or_else(|| anyhow::anyhow!("Session {} not found", session_id))
+    }
+}
+
+#[async_trait]
+impl SessionBackend for MemorySessionBackend {
+    fn path(&self, session_id: &str) -> Result<PathBuf> {
+        storage::validate_session_name(session_id)?;
+        Ok(PathBuf::from("memory").join(format!("{}.jsonl", session_id)))
+    }
+
+    fn exists(&self, session_id: &str) -> Result<bool> {
+        storage::validate_session_name(session_id)?;
+        let sessions = self.sessions()?;
+        Ok(sessions.contains_key(session_id))
+    }
+
+    fn list(&self, sort_order: SortOrder, range: ModifiedRange) -> Result<Vec<SessionInfo>> {
+        let sessions = self.sessions()?;
+        let mut infos = sessions
+            .iter()
+            .filter(|(_, session)| range.contains(session.modified))
+            .map(|(id, session)| {
+                Ok(SessionInfo {
+                    id: id.clone(),
+                    path: self.path(id)?.to_string_lossy().to_string(),
+                    modified: session.modified.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+                    metadata: session.metadata.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        sort_sessions(&mut infos, sort_order);
+        Ok(infos)
+    }
+
+    fn read_metadata(&self, session_id: &str) -> Result<SessionMetadata> {
+        self.with_session(session_id, |session| session.metadata.clone())
+    }
+
+    fn read_messages(&self, session_id: &str) -> Result<Conversation> {
+        self.with_session(session_id, |session| session.conversation.clone())
+    }
+
+    fn revision(&self, session_id: &str) -> Option<String> {
+        self.with_session(session_id, |session| format!("{:x}", session.revision))
+            .ok()
+    }
+
+    fn write(
+        &self,
+        session_id: &str,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<()> {
+        storage::validate_session_name(session_id)?;
+        let mut sessions = self.sessions_mut()?;
+        sessions.insert(
+            session_id.to_string(),
+            self.new_session(metadata, conversation),
+        );
+        Ok(())
+    }
+
+    fn create(
+        &self,
+        session_id: &str,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<bool> {
+        storage::validate_session_name(session_id)?;
+        let mut sessions = self.sessions_mut()?;
+        if sessions.contains_key(session_id) {
+            return Ok(false);
+        }
+        sessions.insert(
+            session_id.to_string(),
+            self.new_session(metadata, conversation),
+        );
+        Ok(true)
+    }
+
+    async fn update_metadata(&self, session_id: &str, metadata: &SessionMetadata) -> Result<()> {
+        let conversation = self.read_messages(session_id)?;
+        self.write(session_id, metadata, &conversation)
+    }
+
+    fn delete(&self, session_id: &str) -> Result<bool> {
+        storage::validate_session_name(session_id)?;
+        let mut sessions = self.sessions_mut()?;
+        Ok(sessions.remove(session_id).is_some())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::session::{self, SessionMetadata};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use std::cmp::Ordering;
 use utoipa::ToSchema;
@@ -18,7 +19,35 @@ pub enum SortOrder {
     Descending,
 }
 
+/// Inclusive bounds on a session's last modified time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModifiedRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ModifiedRange {
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.since.is_none_or(|since| time >= since) && self.until.is_none_or(|until| time <= until)
+    }
+}
+
 pub fn get_valid_sorted_sessions(sort_order: SortOrder) -> Result<Vec<SessionInfo>> {
+    get_valid_sorted_sessions_in_range(sort_order, ModifiedRange::default())
+}
+
+/// Like `get_valid_sorted_sessions`, but only includes sessions modified within `range`.
+///
+/// Sessions are filtered on their file modification time before any metadata is read, and
+/// sessions with an unknown modification time are excluded whenever the range is bounded.
+pub fn get_valid_sorted_sessions_in_range(
+    sort_order: SortOrder,
+    range: ModifiedRange,
+) -> Result<Vec<SessionInfo>> {
     let sessions = match session::list_sessions() {
         Ok(sessions) => sessions,
         Err(e) => {
@@ -32,14 +61,20 @@ pub fn get_valid_sorted_sessions(sort_order: SortOrder) -> Result<Vec<SessionInf
 
     for (id, path) in sessions {
         // Get file modification time with fallback
-        let modified = path
+        let modified_time = path
             .metadata()
             .and_then(|m| m.modified())
-            .map(|time| {
-                chrono::DateTime::<chrono::Utc>::from(time)
-                    .format("%Y-%m-%d %H:%M:%S UTC")
-                    .to_string()
-            })
+            .map(DateTime::<Utc>::from);
+
+        if !range.is_unbounded() {
+            match modified_time {
+                Ok(time) if range.contains(time) => {}
+                _ => continue,
+            }
+        }
+
+        let modified = modified_time
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
             .unwrap_or_else(|_| {
                 tracing::warn!("Failed to get modification time for session: {}", id);
                 "Unknown".to_string()
@@ -77,10 +112,16 @@ pub fn get_valid_sorted_sessions(sort_order: SortOrder) -> Result<Vec<SessionInf
         );
     }
 
+    sort_sessions(&mut session_infos, sort_order);
+    Ok(session_infos)
+}
+
+/// Sort sessions by their modified time, with unknown times last
+pub fn sort_sessions(sessions: &mut [SessionInfo], sort_order: SortOrder) {
     // Sort sessions by modified date
     // Since all dates are in ISO format (YYYY-MM-DD HH:MM:SS UTC), we can just use string comparison
     // This works because the ISO format ensures lexicographical ordering matches chronological ordering
-    session_infos.sort_by(|a, b| {
+    sessions.sort_by(|a, b| {
         if a.modified == "Unknown" && b.modified == "Unknown" {
             return Ordering::Equal;
         } else if a.modified == "Unknown" {
@@ -94,13 +135,13 @@ pub fn get_valid_sorted_sessions(sort_order: SortOrder) -> Result<Vec<SessionInf
             SortOrder::Descending => b.modified.cmp(&a.modified),
         }
     });
-
-    Ok(session_infos)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::ModifiedRange;
     use crate::session::SessionMetadata;
+    use chrono::{TimeZone, Utc};
     use std::fs;
     use tempfile::tempdir;
 
@@ -134,4 +175,30 @@ mod tests {
         // This test verifies the logic changes we made to handle corrupted sessions gracefully
         assert!(true, "Test passes - the function now handles corrupted sessions gracefully by skipping them instead of failing completely");
     }
+
+    #[test]
+    fn test_modified_range_contains() {
+        let t = |day| Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap();
+
+        let unbounded = ModifiedRange::default();
+        assert!(unbounded.is_unbounded());
+        assert!(unbounded.contains(t(1)));
+
+        let range = ModifiedRange {
+            since: Some(t(5)),
+            until: Some(t(10)),
+        };
+        assert!(!range.is_unbounded());
+        assert!(!range.contains(t(4)));
+        assert!(range.contains(t(5)));
+        assert!(range.contains(t(10)));
+        assert!(!range.contains(t(11)));
+
+        let since_only = ModifiedRange {
+            since: Some(t(5)),
+            until: None,
+        };
+        assert!(since_only.contains(t(31)));
+        assert!(!since_only.contains(t(1)));
+    }
 }
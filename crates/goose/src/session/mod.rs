@@ -1,14 +1,19 @@
+pub mod backend;
 pub mod extension_data;
 pub mod info;
 pub mod storage;
+pub mod store;
 
 // Re-export common session types and functions
 pub use storage::{
-    ensure_session_dir, generate_description, generate_description_with_schedule_id,
-    generate_session_id, get_most_recent_session, get_path, list_sessions, persist_messages,
-    persist_messages_with_schedule_id, read_messages, read_metadata, update_metadata, Identifier,
-    SessionMetadata,
+    delete_session_file, ensure_session_dir, fallback_description, generate_description,
+    generate_description_with_schedule_id, generate_session_id, get_most_recent_session, get_path,
+    list_sessions, parse_session_import, persist_messages, persist_messages_with_schedule_id,
+    read_messages, read_metadata, update_metadata, validate_new_session_name, Identifier,
+    SessionMetadata, MAX_IMPORT_SIZE, RESERVED_SESSION_NAMES,
 };
 
+pub use backend::{FileSessionBackend, MemorySessionBackend, SessionBackend};
 pub use extension_data::{ExtensionData, ExtensionState, TodoState};
 pub use info::{get_valid_sorted_sessions, SessionInfo};
+pub use store::{SessionChange, SessionStore};
@@ -14,8 +14,10 @@ use crate::utils::safe_truncate;
 use anyhow::Result;
 use chrono::Local;
 use etcetera::{choose_app_strategy, AppStrategy, AppStrategyArgs};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::io::{self, BufRead, Write};
 use std::ops::DerefMut;
@@ -28,6 +30,14 @@ const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB
 const MAX_MESSAGE_COUNT: usize = 5000;
 const MAX_LINE_LENGTH: usize = 1024 * 1024; // 1MB per line
 
+// The metadata line is padded with spaces to a multiple of this many bytes, with at least
+// METADATA_LINE_SLACK to spare, so it can usually be rewritten in place as messages are appended
+const METADATA_LINE_BLOCK: usize = 512;
+const METADATA_LINE_SLACK: usize = 256;
+
+/// Largest transcript accepted by `parse_session_import`, matching the session file limit
+pub const MAX_IMPORT_SIZE: usize = MAX_FILE_SIZE as usize;
+
 fn get_home_dir() -> PathBuf {
     choose_app_strategy(crate::config::APP_STRATEGY.clone())
         .expect("goose requires a home dir")
@@ -72,6 +82,11 @@ pub struct SessionMetadata {
     pub extension_data: ExtensionData,
 
     pub recipe: Option<Recipe>,
+
+    /// Unix timestamp (seconds) of the last provider response in the session, used as a
+    /// heartbeat to tell live sessions apart from ones that were abandoned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_activity: Option<i64>,
 }
 
 // Custom deserializer to handle old sessions without working_dir
@@ -95,6 +110,7 @@ impl<'de> Deserialize<'de> for SessionMetadata {
             #[serde(default)]
             extension_data: ExtensionData,
             recipe: Option<Recipe>,
+            last_activity: Option<i64>,
         }
 
         let helper = Helper::deserialize(deserializer)?;
@@ -118,6 +134,7 @@ impl<'de> Deserialize<'de> for SessionMetadata {
             working_dir,
             extension_data: helper.extension_data,
             recipe: helper.recipe,
+            last_activity: helper.last_activity,
         })
     }
 }
@@ -144,8 +161,15 @@ impl SessionMetadata {
             accumulated_output_tokens: None,
             extension_data: ExtensionData::new(),
             recipe: None,
+            last_activity: None,
         }
     }
+
+    /// Whether the session has recorded activity but none within `idle_secs` of `now`
+    pub fn is_idle(&self, now: i64, idle_secs: i64) -> bool {
+        self.last_activity
+            .is_some_and(|last_activity| now - last_activity > idle_secs)
+    }
 }
 
 impl Default for SessionMetadata {
@@ -163,18 +187,40 @@ pub enum Identifier {
     Path(PathBuf),
 }
 
+/// Reject session names that are empty, too long or could escape the session directory
+pub fn validate_session_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > 255 {
+        return Err(anyhow::anyhow!("Invalid session name length"));
+    }
+
+    // Check for path traversal attempts
+    if name.contains("..") || name.contains('/') || name.contains('\\') {
+        return Err(anyhow::anyhow!("Invalid characters in session name"));
+    }
+    Ok(())
+}
+
+/// Names used by the server's `/sessions/...` routes, which would hide a session of the
+/// same name from the `/sessions/{session_id}` routes
+pub const RESERVED_SESSION_NAMES: &[&str] = &["latest", "insights", "token-usage", "import"];
+
+/// Check the name of a session being created, which also may not be a reserved name.
+/// Existing sessions with a reserved name can still be read and resumed.
+pub fn validate_new_session_name(name: &str) -> Result<()> {
+    validate_session_name(name)?;
+    if RESERVED_SESSION_NAMES.contains(&name) {
+        return Err(anyhow::anyhow!(
+            "'{}' is reserved and can't be used as a session name",
+            name
+        ));
+    }
+    Ok(())
+}
+
 pub fn get_path(id: Identifier) -> Result<PathBuf> {
     let path = match id {
         Identifier::Name(name) => {
-            // Validate session name for security
-            if name.is_empty() || name.len() > 255 {
-                return Err(anyhow::anyhow!("Invalid session name length"));
-            }
-
-            // Check for path traversal attempts
-            if name.contains("..") || name.contains('/') || name.contains('\\') {
-                return Err(anyhow::anyhow!("Invalid characters in session name"));
-            }
+            validate_session_name(&name)?;
 
             let session_dir = ensure_session_dir().map_err(|e| {
                 tracing::error!("Failed to create session directory: {}", e);
@@ -1141,9 +1187,186 @@ pub async fn persist_messages_with_schedule_id(
     }
 }
 
+/// The metadata line for a session file, padded with spaces to at least `width` bytes
+fn metadata_line(metadata: &SessionMetadata, width: usize) -> Result<String> {
+    let json = serde_json::to_string(metadata).map_err(|e| {
+        tracing::error!("Failed to serialize metadata: {}", e);
+        anyhow::anyhow!("Failed to write session metadata")
+    })?;
+    let width = width.max(json.len());
+    Ok(format!("{:<width$}", json, width = width))
+}
+
+/// What the last save left in a session file, so the next save of the same conversation
+/// can append to it without reading or re-serializing the stored messages
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StoredMessages {
+    count: usize,
+    /// Length of the file in bytes
+    len: u64,
+    /// Width of the padded metadata line, without its line break
+    header_width: usize,
+    /// Hashes of the first and last stored message lines
+    first: u64,
+    last: u64,
+}
+
+// Sessions tracked at once; past this the tracking is cleared and the next save of each
+// session rewrites it in full
+const MAX_TRACKED_SESSION_FILES: usize = 256;
+
+/// The messages each session file was last saved with by this process
+static STORED_MESSAGES: Lazy<std::sync::Mutex<HashMap<PathBuf, StoredMessages>>> =
+    Lazy::new(Default::default);
+
+fn stored_messages(session_file: &Path) -> Option<StoredMessages> {
+    STORED_MESSAGES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(session_file)
+        .copied()
+}
+
+fn track_stored_messages(session_file: &Path, stored: Option<StoredMessages>) {
+    let mut tracked = STORED_MESSAGES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match stored {
+        Some(stored) => {
+            if tracked.len() >= MAX_TRACKED_SESSION_FILES && !tracked.contains_key(session_file) {
+                tracked.clear();
+            }
+            tracked.insert(session_file.to_path_buf(), stored);
+        }
+        None => {
+            tracked.remove(session_file);
+        }
+    }
+}
+
+fn line_hash(line: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    line.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn message_hash(message: &Message) -> Result<u64> {
+    Ok(line_hash(&serde_json::to_string(message)?))
+}
+
+/// Append the messages in `conversation` past those already stored in a session file,
+/// rewriting the metadata line in place
+///
+/// Only used when this process wrote the file last, it still has the length it was left
+/// with, `conversation` is at least as long as what was stored and starts and ends its
+/// stored part with the same messages, and the new metadata fits in the padded metadata
+/// line. Conversations are only ever extended or replaced, and messages carry their own
+/// id and timestamp, so the first and last stored messages stand in for the rest. Returns
+/// false without writing anything otherwise, so the caller can rewrite the whole file.
+///
+/// Neither step is atomic. The new messages are written before the metadata line, so a
+/// crash in between leaves them under the previous metadata. A crash while the metadata
+/// line is rewritten can leave it unreadable, in which case the session reads with
+/// default metadata and all of its messages until the next full rewrite.
+fn append_messages_with_metadata(
+    session_file: &Path,
+    metadata: &SessionMetadata,
+    conversation: &Conversation,
+) -> Result<bool> {
+    use fs2::FileExt;
+
+    let Some(stored) = stored_messages(session_file) else {
+        return Ok(false);
+    };
+    let file = match fs::OpenOptions::new().write(true).open(session_file) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    if file.metadata()?.len() > MAX_FILE_SIZE || file.try_lock_exclusive().is_err() {
+        return Ok(false);
+    }
+
+    // Anything unexpected falls back to a full rewrite, which also repairs a partial append
+    let appended = append_to_locked_file(session_file, &file, stored, metadata, conversation)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to append to session file, rewriting it: {}", e);
+            false
+        });
+    let _ = fs2::FileExt::unlock(&file);
+    Ok(appended)
+}
+
+fn append_to_locked_file(
+    session_file: &Path,
+    mut file: &fs::File,
+    stored: StoredMessages,
+    metadata: &SessionMetadata,
+    conversation: &Conversation,
+) -> Result<bool> {
+    use std::io::{Seek, SeekFrom};
+
+    let messages = conversation.messages();
+    if file.metadata()?.len() != stored.len || messages.len() < stored.count {
+        return Ok(false);
+    }
+    if let (Some(first), Some(last)) = (
+        messages.first().filter(|_| stored.count > 0),
+        stored.count.checked_sub(1).map(|index| &messages[index]),
+    ) {
+        if message_hash(first)? != stored.first || message_hash(last)? != stored.last {
+            return Ok(false);
+        }
+    }
+
+    let header = metadata_line(metadata, stored.header_width)?;
+    if header.len() > stored.header_width {
+        return Ok(false);
+    }
+
+    let mut new_lines = String::new();
+    let mut first = stored.first;
+    let mut last = stored.last;
+    for (index, message) in messages.iter().enumerate().skip(stored.count) {
+        let line = serde_json::to_string(message)?;
+        last = line_hash(&line);
+        if index == 0 {
+            first = last;
+        }
+        new_lines.push_str(&line);
+        new_lines.push('\n');
+    }
+
+    // Forget the file until both writes are done, so a failure part way is rewritten
+    track_stored_messages(session_file, None);
+    file.seek(SeekFrom::Start(stored.len))?;
+    file.write_all(new_lines.as_bytes())?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(header.as_bytes())?;
+    file.sync_all()?;
+    track_stored_messages(
+        session_file,
+        Some(StoredMessages {
+            count: messages.len(),
+            len: stored.len + new_lines.len() as u64,
+            header_width: stored.header_width,
+            first,
+            last,
+        }),
+    );
+    Ok(true)
+}
+
 /// Write messages to a session file with the provided metadata using secure atomic operations
 ///
-/// This function uses atomic file operations to prevent corruption:
+/// When this process last saved the file with the start of `conversation`, only the new
+/// messages are appended and the metadata line is updated in place, so long sessions
+/// aren't rewritten on every message. An append is not atomic; see
+/// `append_messages_with_metadata` for what a crash part way through leaves behind.
+/// Otherwise the whole file is rewritten.
+///
+/// A full rewrite uses atomic file operations to prevent corruption:
 /// 1. Writes to a temporary file first with secure permissions
 /// 2. Uses fs2 file locking to prevent concurrent writes
 /// 3. Atomically moves the temp file to the final location
@@ -1173,6 +1396,11 @@ pub fn save_messages_with_metadata(
         return Err(anyhow::anyhow!("Too many messages to save"));
     }
 
+    if append_messages_with_metadata(&secure_path, metadata, conversation)? {
+        tracing::debug!("Appended to session file: {:?}", secure_path);
+        return Ok(());
+    }
+
     // Create a temporary file in the same directory to ensure atomic move
     let temp_file = secure_path.with_extension("tmp");
 
@@ -1214,31 +1442,7 @@ pub fn save_messages_with_metadata(
     })?;
 
     // Write to temporary file
-    {
-        let mut writer = io::BufWriter::new(&file);
-
-        // Write metadata as the first line
-        serde_json::to_writer(&mut writer, &metadata).map_err(|e| {
-            tracing::error!("Failed to serialize metadata: {}", e);
-            anyhow::anyhow!("Failed to write session metadata")
-        })?;
-        writeln!(writer)?;
-
-        // Write all messages with progress tracking
-        for (i, message) in conversation.iter().enumerate() {
-            serde_json::to_writer(&mut writer, &message).map_err(|e| {
-                tracing::error!("Failed to serialize message {}: {}", i, e);
-                anyhow::anyhow!("Failed to write session message")
-            })?;
-            writeln!(writer)?;
-        }
-
-        // Ensure all data is written to disk
-        writer.flush().map_err(|e| {
-            tracing::error!("Failed to flush writer: {}", e);
-            anyhow::anyhow!("Failed to flush session data")
-        })?;
-    }
+    let stored = write_session_contents(&file, metadata, conversation)?;
 
     // Sync to ensure data is persisted
     file.sync_all().map_err(|e| {
@@ -1253,17 +1457,97 @@ pub fn save_messages_with_metadata(
     })?;
 
     // Atomically move the temporary file to the final location
+    track_stored_messages(&secure_path, None);
     fs::rename(&temp_file, &secure_path).map_err(|e| {
         // Clean up temp file on failure
         tracing::error!("Failed to move temporary file: {}", e);
         let _ = fs::remove_file(&temp_file);
         anyhow::anyhow!("Failed to finalize session file")
     })?;
+    track_stored_messages(&secure_path, Some(stored));
 
     tracing::debug!("Successfully saved session file: {:?}", secure_path);
     Ok(())
 }
 
+/// Write a session's metadata line and messages to `file`, returning what was stored
+fn write_session_contents(
+    file: &fs::File,
+    metadata: &SessionMetadata,
+    conversation: &Conversation,
+) -> Result<StoredMessages> {
+    let mut writer = io::BufWriter::new(file);
+
+    // Write metadata as the first line, padded so later appends can update it in place
+    let header = metadata_line(metadata, 0)?;
+    let width =
+        (header.len() + METADATA_LINE_SLACK).div_ceil(METADATA_LINE_BLOCK) * METADATA_LINE_BLOCK;
+    writeln!(writer, "{:<width$}", header, width = width)?;
+    let mut stored = StoredMessages {
+        count: conversation.len(),
+        len: width as u64 + 1,
+        header_width: width,
+        first: 0,
+        last: 0,
+    };
+
+    // Write all messages with progress tracking
+    for (i, message) in conversation.iter().enumerate() {
+        let line = serde_json::to_string(&message).map_err(|e| {
+            tracing::error!("Failed to serialize message {}: {}", i, e);
+            anyhow::anyhow!("Failed to write session message")
+        })?;
+        writeln!(writer, "{}", line)?;
+        stored.len += line.len() as u64 + 1;
+        stored.last = line_hash(&line);
+        if i == 0 {
+            stored.first = stored.last;
+        }
+    }
+
+    // Ensure all data is written to disk
+    writer.flush().map_err(|e| {
+        tracing::error!("Failed to flush writer: {}", e);
+        anyhow::anyhow!("Failed to flush session data")
+    })?;
+    Ok(stored)
+}
+
+/// Create a session file, returning false without touching it if it already exists
+///
+/// The session is written to a temporary file that is then moved into place only if
+/// nothing is there yet, so the check can't be split from the creation and readers never
+/// see a partly written session.
+pub fn create_session_file(
+    session_file: &Path,
+    metadata: &SessionMetadata,
+    conversation: &Conversation,
+) -> Result<bool> {
+    let secure_path = get_path(Identifier::Path(session_file.to_path_buf()))?;
+    if conversation.len() > MAX_MESSAGE_COUNT {
+        return Err(anyhow::anyhow!("Too many messages to save"));
+    }
+
+    let parent = secure_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid session file path"))?;
+    fs::create_dir_all(parent)?;
+
+    // Temporary files are only readable by their owner
+    let temp_file = tempfile::NamedTempFile::new_in(parent)?;
+    let stored = write_session_contents(temp_file.as_file(), metadata, conversation)?;
+    temp_file.as_file().sync_all()?;
+
+    match temp_file.persist_noclobber(&secure_path) {
+        Ok(_) => {
+            track_stored_messages(&secure_path, Some(stored));
+            Ok(true)
+        }
+        Err(e) if e.error.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e.error.into()),
+    }
+}
+
 /// Generate a description for the session using the provider
 ///
 /// This function is called when appropriate to generate a short description
@@ -1340,6 +1624,21 @@ pub async fn generate_description_with_schedule_id(
     save_messages_with_metadata(&secure_path, &metadata, messages)
 }
 
+/// Characters of the first user message kept when describing a session without a provider
+pub const FALLBACK_DESCRIPTION_CHARS: usize = 60;
+
+/// Describe a session by the start of its first non-empty user message, for when no
+/// provider is available to name it
+pub fn fallback_description(messages: &Conversation) -> Option<String> {
+    let text = messages
+        .iter()
+        .filter(|m| m.role == rmcp::model::Role::User)
+        .map(|m| m.as_concat_text())
+        .find(|text| !text.trim().is_empty())?;
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(safe_truncate(&collapsed, FALLBACK_DESCRIPTION_CHARS))
+}
+
 /// Update only the metadata in a session file, preserving all messages
 ///
 /// Security features:
@@ -1356,6 +1655,116 @@ pub async fn update_metadata(session_file: &Path, metadata: &SessionMetadata) ->
     save_messages_with_metadata(&secure_path, metadata, &messages)
 }
 
+/// Locks held while a session file's metadata is read, changed and written back
+static METADATA_LOCKS: Lazy<std::sync::Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>> =
+    Lazy::new(Default::default);
+
+/// Lock a session file's metadata for a read-modify-write update
+///
+/// Updates to the same file in this process wait for each other, so one can't overwrite
+/// the fields another just changed. The lock is advisory: `update_metadata` on its own
+/// doesn't take it.
+pub async fn lock_metadata(session_file: &Path) -> tokio::sync::OwnedMutexGuard<()> {
+    let lock = {
+        let mut locks = METADATA_LOCKS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Forget locks nobody holds or waits on, so the map doesn't grow with every session
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+        locks.entry(session_file.to_path_buf()).or_default().clone()
+    };
+    lock.lock_owned().await
+}
+
+/// Read a session file's metadata, apply `update` and write it back, preserving all
+/// messages, while holding the file's metadata lock. Returns the metadata as written.
+pub async fn modify_metadata(
+    session_file: &Path,
+    update: impl FnOnce(&mut SessionMetadata) + Send,
+) -> Result<SessionMetadata> {
+    let _guard = lock_metadata(session_file).await;
+    let mut metadata = read_metadata(session_file)?;
+    update(&mut metadata);
+    update_metadata(session_file, &metadata).await?;
+    Ok(metadata)
+}
+
+/// Delete a session file, returning false if it did not exist
+///
+/// The file is first renamed out of the way so that concurrent readers either see the
+/// complete session or no session at all, never a partially removed one.
+pub fn delete_session_file(session_file: &Path) -> Result<bool> {
+    let tombstone = session_file.with_extension("deleting");
+
+    match fs::rename(session_file, &tombstone) {
+        Ok(()) => track_stored_messages(session_file, None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => {
+            tracing::error!("Failed to move session file for deletion: {}", e);
+            return Err(anyhow::anyhow!("Failed to delete session file"));
+        }
+    }
+
+    fs::remove_file(&tombstone).map_err(|e| {
+        tracing::error!("Failed to remove session file: {}", e);
+        anyhow::anyhow!("Failed to delete session file")
+    })?;
+
+    Ok(true)
+}
+
+/// A session as returned by the session history API
+#[derive(Deserialize)]
+struct SessionExport {
+    metadata: SessionMetadata,
+    messages: Vec<Message>,
+}
+
+/// Parse an exported session transcript so it can be imported
+///
+/// Accepts either the session file format (metadata on the first line followed by one
+/// message per line) or a JSON object with `metadata` and `messages`. Unlike reading a
+/// session file nothing is recovered or truncated: any malformed line rejects the import.
+pub fn parse_session_import(data: &str) -> Result<(SessionMetadata, Conversation)> {
+    if data.len() > MAX_IMPORT_SIZE {
+        return Err(anyhow::anyhow!(
+            "Session import is too large ({} bytes, max {})",
+            data.len(),
+            MAX_IMPORT_SIZE
+        ));
+    }
+
+    let (metadata, messages) = match serde_json::from_str::<SessionExport>(data) {
+        Ok(export) => (export.metadata, export.messages),
+        Err(_) => {
+            let mut lines = data.lines().filter(|line| !line.trim().is_empty());
+            let metadata_line = lines
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Session import is empty"))?;
+            let metadata = serde_json::from_str::<SessionMetadata>(metadata_line)
+                .map_err(|e| anyhow::anyhow!("Invalid session metadata: {}", e))?;
+            let messages = lines
+                .enumerate()
+                .map(|(i, line)| {
+                    serde_json::from_str::<Message>(line)
+                        .map_err(|e| anyhow::anyhow!("Invalid message {}: {}", i + 1, e))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            (metadata, messages)
+        }
+    };
+
+    if messages.len() > MAX_MESSAGE_COUNT {
+        return Err(anyhow::anyhow!(
+            "Session import has too many messages ({}, max {})",
+            messages.len(),
+            MAX_MESSAGE_COUNT
+        ));
+    }
+
+    Ok((metadata, Conversation::new_unvalidated(messages)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1483,6 +1892,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_validate_new_session_name() {
+        assert!(validate_new_session_name("my-session").is_ok());
+        assert!(validate_new_session_name(&generate_session_id()).is_ok());
+        assert!(validate_new_session_name("../escape").is_err());
+        for name in RESERVED_SESSION_NAMES {
+            assert!(validate_new_session_name(name).is_err());
+            // Sessions saved under a reserved name before it was reserved still open
+            assert!(validate_session_name(name).is_ok());
+        }
+    }
+
     #[test]
     fn test_generate_session_id() {
         let id = generate_session_id();
@@ -1983,4 +2404,224 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_save_appends_new_messages() -> Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.jsonl");
+        let mut metadata = SessionMetadata::default();
+        let mut messages = Conversation::new_unvalidated(vec![
+            Message::user().with_text("Hello"),
+            Message::assistant().with_text("Hi there"),
+        ]);
+        save_messages_with_metadata(&file_path, &metadata, &messages)?;
+
+        let header_len = |path: &Path| -> Result<usize> {
+            let contents = fs::read_to_string(path)?;
+            Ok(contents.lines().next().unwrap_or_default().len())
+        };
+        let first_header_len = header_len(&file_path)?;
+        assert_eq!(first_header_len % METADATA_LINE_BLOCK, 0);
+        #[cfg(unix)]
+        let inode = {
+            use std::os::unix::fs::MetadataExt;
+            fs::metadata(&file_path)?.ino()
+        };
+
+        // Adding a message appends it and updates the metadata line in place
+        messages.push(Message::user().with_text("Another question"));
+        metadata.description = "Greetings".to_string();
+        save_messages_with_metadata(&file_path, &metadata, &messages)?;
+        assert_eq!(header_len(&file_path)?, first_header_len);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            assert_eq!(fs::metadata(&file_path)?.ino(), inode);
+        }
+        assert_eq!(read_metadata(&file_path)?.description, "Greetings");
+        let read = read_messages(&file_path)?;
+        assert_eq!(read.len(), 3);
+        assert_eq!(read.last().unwrap().as_concat_text(), "Another question");
+
+        // Metadata that outgrows the padding rewrites the whole file with a wider line
+        messages.push(Message::assistant().with_text("A long answer"));
+        metadata.description = "d".repeat(METADATA_LINE_BLOCK * 2);
+        save_messages_with_metadata(&file_path, &metadata, &messages)?;
+        let grown_header_len = header_len(&file_path)?;
+        assert!(grown_header_len > first_header_len);
+        assert_eq!(grown_header_len % METADATA_LINE_BLOCK, 0);
+        assert_eq!(read_metadata(&file_path)?.description, metadata.description);
+        let read = read_messages(&file_path)?;
+        assert_eq!(read.len(), 4);
+        assert_eq!(read.last().unwrap().as_concat_text(), "A long answer");
+
+        // The next message is appended under the wider line again
+        messages.push(Message::user().with_text("Thanks"));
+        save_messages_with_metadata(&file_path, &metadata, &messages)?;
+        assert_eq!(header_len(&file_path)?, grown_header_len);
+        assert_eq!(read_messages(&file_path)?.len(), 5);
+
+        // A conversation that no longer starts with the stored messages is rewritten
+        let replaced =
+            Conversation::new_unvalidated(vec![Message::user().with_text("Summary so far")]);
+        save_messages_with_metadata(&file_path, &metadata, &replaced)?;
+        let read = read_messages(&file_path)?;
+        assert_eq!(read.len(), 1);
+        assert_eq!(read.first().unwrap().as_concat_text(), "Summary so far");
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_modify_metadata_keeps_every_update() -> Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.jsonl");
+        let messages = Conversation::new_unvalidated(vec![Message::user().with_text("Hello")]);
+        save_messages_with_metadata(&file_path, &SessionMetadata::default(), &messages)?;
+
+        // Token updates and a rename racing each other must all land
+        let mut tasks = Vec::new();
+        for _ in 0..16 {
+            let path = file_path.clone();
+            tasks.push(tokio::spawn(async move {
+                modify_metadata(&path, |metadata| {
+                    metadata.message_count += 1;
+                    metadata.accumulated_total_tokens =
+                        Some(metadata.accumulated_total_tokens.unwrap_or(0) + 10);
+                })
+                .await
+            }));
+        }
+        let path = file_path.clone();
+        tasks.push(tokio::spawn(async move {
+            modify_metadata(&path, |metadata| {
+                metadata.description = "Renamed".to_string();
+            })
+            .await
+        }));
+        for task in tasks {
+            task.await??;
+        }
+
+        let metadata = read_metadata(&file_path)?;
+        assert_eq!(metadata.message_count, 16);
+        assert_eq!(metadata.accumulated_total_tokens, Some(160));
+        assert_eq!(metadata.description, "Renamed");
+        assert_eq!(read_messages(&file_path)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_session_file_never_replaces_a_session() -> Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.jsonl");
+        let metadata = SessionMetadata::default();
+        let first = Conversation::new_unvalidated(vec![Message::user().with_text("first")]);
+        let second = Conversation::new_unvalidated(vec![
+            Message::user().with_text("second"),
+            Message::assistant().with_text("reply"),
+        ]);
+
+        assert!(create_session_file(&file_path, &metadata, &first)?);
+        assert!(!create_session_file(&file_path, &metadata, &second)?);
+        assert_eq!(read_messages(&file_path)?.len(), 1);
+
+        // Only the session itself is left behind
+        assert_eq!(fs::read_dir(dir.path())?.count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_session_file() -> Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.jsonl");
+        fs::write(&file_path, "{}\n")?;
+
+        assert!(delete_session_file(&file_path)?);
+        assert!(!file_path.exists());
+        assert!(!file_path.with_extension("deleting").exists());
+
+        // Deleting again reports the session as missing rather than failing
+        assert!(!delete_session_file(&file_path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_last_activity_heartbeat() -> Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("test.jsonl");
+
+        let mut metadata = SessionMetadata::default();
+        // Sessions without a heartbeat are never reported as idle
+        assert!(!metadata.is_idle(10_000, 60));
+
+        metadata.last_activity = Some(1_000);
+        assert!(!metadata.is_idle(1_060, 60));
+        assert!(metadata.is_idle(1_061, 60));
+
+        let messages = Conversation::new_unvalidated(vec![Message::user().with_text("test")]);
+        save_messages_with_metadata(&file_path, &metadata, &messages)?;
+        assert_eq!(read_metadata(&file_path)?.last_activity, Some(1_000));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fallback_description() {
+        let messages = Conversation::new_unvalidated(vec![
+            Message::assistant().with_text("How can I help?"),
+            Message::user().with_text("  "),
+            Message::user().with_text("Fix the   flaky\nlogin test"),
+        ]);
+        assert_eq!(
+            fallback_description(&messages),
+            Some("Fix the flaky login test".to_string())
+        );
+
+        let long = Conversation::new_unvalidated(vec![Message::user().with_text("a".repeat(200))]);
+        assert_eq!(
+            fallback_description(&long).unwrap().chars().count(),
+            FALLBACK_DESCRIPTION_CHARS
+        );
+
+        let no_user = Conversation::new_unvalidated(vec![Message::assistant().with_text("hi")]);
+        assert_eq!(fallback_description(&no_user), None);
+    }
+
+    #[test]
+    fn test_parse_session_import() -> Result<()> {
+        let metadata = SessionMetadata {
+            description: "Imported".to_string(),
+            ..SessionMetadata::default()
+        };
+        let messages = vec![
+            Message::user().with_text("hello"),
+            Message::assistant().with_text("hi"),
+        ];
+
+        // The session file format round-trips
+        let dir = tempdir()?;
+        let file_path = dir.path().join("export.jsonl");
+        save_messages_with_metadata(
+            &file_path,
+            &metadata,
+            &Conversation::new_unvalidated(messages.clone()),
+        )?;
+        let (imported, conversation) = parse_session_import(&fs::read_to_string(&file_path)?)?;
+        assert_eq!(imported.description, "Imported");
+        assert_eq!(conversation.messages(), &messages);
+
+        // So does the history API's JSON shape
+        let json = serde_json::json!({ "metadata": metadata, "messages": messages }).to_string();
+        let (_, conversation) = parse_session_import(&json)?;
+        assert_eq!(conversation.len(), 2);
+
+        // Malformed payloads are rejected instead of recovered
+        let corrupt = format!(
+            "{}\n{{\"role\": \"user\"",
+            serde_json::to_string(&metadata)?
+        );
+        assert!(parse_session_import(&corrupt).is_err());
+        assert!(parse_session_import("").is_err());
+        assert!(parse_session_import(&" ".repeat(MAX_IMPORT_SIZE + 1)).is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,269 @@
+use crate::conversation::message::Message;
+use crate::conversation::Conversation;
+use crate::providers::base::Provider;
+use crate::session::backend::{FileSessionBackend, SessionBackend};
+use crate::session::info::{ModifiedRange, SessionInfo, SortOrder};
+use crate::session::storage::{self, SessionMetadata};
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Number of change notifications buffered for slow subscribers
+const SESSION_CHANGE_CAPACITY: usize = 64;
+
+/// A change made to a session through a `SessionStore`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionChange {
+    Updated(String),
+    Deleted(String),
+}
+
+/// Thin wrapper around a `SessionBackend` that notifies subscribers of changes
+///
+/// Anything that caches session data (session lists, insights, search indexes) can
+/// subscribe and invalidate its entries when a session is updated or deleted.
+#[derive(Clone)]
+pub struct SessionStore {
+    backend: Arc<dyn SessionBackend>,
+    changes: broadcast::Sender<SessionChange>,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionStore {
+    /// A store backed by the session directory
+    pub fn new() -> Self {
+        Self::with_backend(Arc::new(FileSessionBackend))
+    }
+
+    pub fn with_backend(backend: Arc<dyn SessionBackend>) -> Self {
+        let (changes, _) = broadcast::channel(SESSION_CHANGE_CAPACITY);
+        Self { backend, changes }
+    }
+
+    /// Subscribe to changes made through this store
+    pub fn subscribe(&self) -> broadcast::Receiver<SessionChange> {
+        self.changes.subscribe()
+    }
+
+    /// Resolve where a session is stored, validating the session id
+    pub fn path(&self, session_id: &str) -> Result<PathBuf> {
+        self.backend.path(session_id)
+    }
+
+    pub fn exists(&self, session_id: &str) -> Result<bool> {
+        self.backend.exists(session_id)
+    }
+
+    /// Sessions modified within `range`, in `sort_order` by modified time
+    pub fn list(&self, sort_order: SortOrder, range: ModifiedRange) -> Result<Vec<SessionInfo>> {
+        self.backend.list(sort_order, range)
+    }
+
+    pub fn read_metadata(&self, session_id: &str) -> Result<SessionMetadata> {
+        self.backend.read_metadata(session_id)
+    }
+
+    pub fn read_messages(&self, session_id: &str) -> Result<Conversation> {
+        self.backend.read_messages(session_id)
+    }
+
+    /// A token that changes whenever the session does, for cheap change detection
+    pub fn revision(&self, session_id: &str) -> Option<String> {
+        self.backend.revision(session_id)
+    }
+
+    /// Replace a session's metadata, preserving its messages
+    pub async fn update_metadata(
+        &self,
+        session_id: &str,
+        metadata: &SessionMetadata,
+    ) -> Result<()> {
+        self.backend.update_metadata(session_id, metadata).await?;
+        self.notify(SessionChange::Updated(session_id.to_string()));
+        Ok(())
+    }
+
+    /// Change a session's metadata with `update`, preserving its messages
+    ///
+    /// The metadata is read and written back under the session's metadata lock, so
+    /// concurrent updates, such as a rename while the agent records token usage, don't
+    /// lose each other's changes.
+    pub async fn modify_metadata(
+        &self,
+        session_id: &str,
+        update: impl FnOnce(&mut SessionMetadata) + Send,
+    ) -> Result<SessionMetadata> {
+        let _guard = storage::lock_metadata(&self.backend.path(session_id)?).await;
+        let mut metadata = self.read_metadata(session_id)?;
+        update(&mut metadata);
+        self.update_metadata(session_id, &metadata).await?;
+        Ok(metadata)
+    }
+
+    /// Append `messages` to a session, preserving its metadata
+    ///
+    /// The session is read and written back under its metadata lock, so appends and
+    /// metadata updates made through this store don't lose each other's changes.
+    pub async fn append_messages(&self, session_id: &str, messages: Vec<Message>) -> Result<()> {
+        let _guard = storage::lock_metadata(&self.backend.path(session_id)?).await;
+        let metadata = self.read_metadata(session_id)?;
+        let mut conversation = self.read_messages(session_id)?;
+        conversation.extend(messages);
+        self.backend.write(session_id, &metadata, &conversation)?;
+        self.notify(SessionChange::Updated(session_id.to_string()));
+        Ok(())
+    }
+
+    /// Give a session without a description one, returning the new description
+    ///
+    /// The provider names the session when one is given; without one, or when it fails,
+    /// the start of the first user message is used. Sessions that already have a
+    /// description or have no user messages are left unchanged.
+    pub async fn describe_if_missing(
+        &self,
+        session_id: &str,
+        provider: Option<Arc<dyn Provider>>,
+    ) -> Result<Option<String>> {
+        if !self.read_metadata(session_id)?.description.is_empty() {
+            return Ok(None);
+        }
+
+        let messages = self.read_messages(session_id)?;
+        let mut description = None;
+        if let Some(provider) = provider {
+            match provider.generate_session_name(&messages).await {
+                Ok(name) if !name.trim().is_empty() => description = Some(name),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to generate a description for session {}: {}",
+                    session_id,
+                    e
+                ),
+            }
+        }
+
+        let Some(description) = description.or_else(|| storage::fallback_description(&messages))
+        else {
+            return Ok(None);
+        };
+        // Another update may have named the session while the provider was generating
+        let metadata = self
+            .modify_metadata(session_id, |metadata| {
+                if metadata.description.is_empty() {
+                    metadata.description = description.clone();
+                }
+            })
+            .await?;
+        Ok((metadata.description == description).then_some(description))
+    }
+
+    /// Save an imported session under a fresh id, returning the id and path
+    ///
+    /// The id never matches an existing session, even one created at the same moment, so
+    /// importing never overwrites one.
+    pub fn import(
+        &self,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<(String, PathBuf)> {
+        self.create(metadata, conversation)
+    }
+
+    /// Save a new session under a fresh id, returning the id and path
+    pub fn create(
+        &self,
+        metadata: &SessionMetadata,
+        conversation: &Conversation,
+    ) -> Result<(String, PathBuf)> {
+        let base_id = storage::generate_session_id();
+        let mut session_id = base_id.clone();
+        let mut suffix = 1;
+        while !self.backend.create(&session_id, metadata, conversation)? {
+            suffix += 1;
+            session_id = format!("{}_{}", base_id, suffix);
+        }
+
+        let path = self.path(&session_id)?;
+        self.notify(SessionChange::Updated(session_id.clone()));
+        Ok((session_id, path))
+    }
+
+    /// Delete a session, returning false if it did not exist
+    pub fn delete(&self, session_id: &str) -> Result<bool> {
+        let deleted = self.backend.delete(session_id)?;
+        if deleted {
+            self.notify(SessionChange::Deleted(session_id.to_string()));
+        }
+        Ok(deleted)
+    }
+
+    fn notify(&self, change: SessionChange) {
+        // Having no subscribers is fine, there is simply nothing to invalidate
+        let _ = self.changes.send(change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::backend::MemorySessionBackend;
+
+    #[tokio::test]
+    async fn test_notify_reaches_subscribers() {
+        let store = SessionStore::new();
+
+        // Sending without subscribers must not fail
+        store.notify(SessionChange::Updated("early".to_string()));
+
+        let mut changes = store.subscribe();
+        store.notify(SessionChange::Deleted("20250101_120000".to_string()));
+        assert_eq!(
+            changes.recv().await.unwrap(),
+            SessionChange::Deleted("20250101_120000".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_round_trip() {
+        let store = SessionStore::with_backend(Arc::new(MemorySessionBackend::new()));
+        let mut changes = store.subscribe();
+
+        let (session_id, _) = store
+            .import(&SessionMetadata::default(), &Conversation::empty())
+            .unwrap();
+        assert!(store.exists(&session_id).unwrap());
+        assert_eq!(
+            changes.recv().await.unwrap(),
+            SessionChange::Updated(session_id.clone())
+        );
+
+        let revision = store.revision(&session_id);
+        let mut metadata = store.read_metadata(&session_id).unwrap();
+        metadata.description = "Renamed".to_string();
+        store.update_metadata(&session_id, &metadata).await.unwrap();
+        assert_ne!(store.revision(&session_id), revision);
+
+        let sessions = store
+            .list(SortOrder::Descending, ModifiedRange::default())
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].metadata.description, "Renamed");
+
+        assert!(store.delete(&session_id).unwrap());
+        assert!(!store.delete(&session_id).unwrap());
+        assert!(!store.exists(&session_id).unwrap());
+        assert!(store.read_messages(&session_id).is_err());
+    }
+
+    #[test]
+    fn test_path_rejects_invalid_ids() {
+        let store = SessionStore::new();
+        assert!(store.path("../outside").is_err());
+    }
+}
@@ -895,6 +895,7 @@ mod retry_tests {
             execution_mode: None,
             max_turns: None,
             retry_config: Some(retry_config),
+            max_history_messages: None,
         };
 
         let conversation =
@@ -1074,6 +1075,7 @@ mod max_turns_tests {
             execution_mode: None,
             max_turns: Some(1),
             retry_config: None,
+            max_history_messages: None,
         };
         let conversation = Conversation::new(vec![Message::user().with_text("Hello")]).unwrap();
 
@@ -33,6 +33,8 @@ fn test_task_serialization_with_enum() {
         id: "test-id".to_string(),
         task_type: TaskType::InlineRecipe,
         payload: json!({"recipe": "test"}),
+        group: None,
+        tags: Vec::new(),
     };
 
     let serialized = serde_json::to_value(&task).unwrap();
@@ -74,6 +76,8 @@ fn test_task_methods_with_sub_recipe() {
                 "sequential_when_repeated": true
             }
         }),
+        group: None,
+        tags: Vec::new(),
     };
 
     assert!(task.get_sub_recipe().is_some());
@@ -94,6 +98,8 @@ fn test_task_methods_with_inline_recipe() {
             },
             "return_last_only": true
         }),
+        group: None,
+        tags: Vec::new(),
     };
 
     assert!(task.get_sub_recipe().is_none());
@@ -116,6 +122,8 @@ fn test_task_with_missing_fields() {
         id: "test-4".to_string(),
         task_type: TaskType::SubRecipe,
         payload: json!({}), // Missing sub_recipe field
+        group: None,
+        tags: Vec::new(),
     };
 
     assert!(task.get_sub_recipe().is_none());
@@ -413,5 +413,6 @@ pub fn create_test_session_metadata(message_count: usize, working_dir: &str) ->
         accumulated_output_tokens: Some(50),
         extension_data: Default::default(),
         recipe: None,
+        last_activity: None,
     }
 }